@@ -0,0 +1,394 @@
+//! Decode GDL90 frames from a file, stdin or a UDP port and print each payload
+//!
+//! Requires the `cli` feature: `cargo run --features cli --bin gdl90dump -- --help`
+use gdl90codec::{message::Gdl90Framer, payload::Payload, report::Report};
+use serde_json::Value;
+use std::{
+    env,
+    fs::File,
+    io::{self, Read, Write},
+    net::UdpSocket,
+    process::ExitCode,
+};
+
+const USAGE: &str = "Usage: gdl90dump [OPTIONS] [FILE]
+
+Decode GDL90 frames and print each payload on one line, in engineering
+units. Reads FILE, or stdin if FILE is omitted or '-'.
+
+Options:
+  -u, --udp [PORT]        Listen on a UDP port instead (default 4000)
+  -j, --json              Print one JSON object per line
+  -m, --message-id ID     Only print messages with this ID (repeatable)
+  -a, --address ADDRESS   Only print ownship/traffic reports for this
+                          participant address (repeatable)
+  -h, --help              Print this help
+
+IDs and addresses are decimal, or hexadecimal with a 0x prefix.";
+
+const DEFAULT_UDP_PORT: u16 = 4000;
+
+#[derive(Debug, Default, PartialEq)]
+enum Source {
+    #[default]
+    Stdin,
+    File(String),
+    Udp(u16),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    source: Source,
+    json: bool,
+    message_ids: Vec<u8>,
+    addresses: Vec<u32>,
+}
+
+impl Options {
+    /// Parse the command line arguments, `None` if help was requested
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter().peekable();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-j" | "--json" => options.json = true,
+                "-u" | "--udp" => {
+                    let port = args.next_if(|arg| parse_number::<u16>(arg).is_ok());
+                    options.source = Source::Udp(match port {
+                        Some(port) => parse_number(&port)?,
+                        None => DEFAULT_UDP_PORT,
+                    });
+                }
+                "-m" | "--message-id" => {
+                    let id = args.next().ok_or("Missing message ID")?;
+                    options.message_ids.push(parse_number(&id)?);
+                }
+                "-a" | "--address" => {
+                    let address = args.next().ok_or("Missing address")?;
+                    options.addresses.push(parse_number(&address)?);
+                }
+                "-" => options.source = Source::Stdin,
+                arg if arg.starts_with('-') => return Err(format!("Unknown option {arg}")),
+                path => options.source = Source::File(path.to_owned()),
+            }
+        }
+
+        Ok(Some(options))
+    }
+
+    /// True if the message passes the message ID and address filters
+    fn matches(&self, message_id: u8, payload: &Payload) -> bool {
+        if !self.message_ids.is_empty() && !self.message_ids.contains(&message_id) {
+            return false;
+        }
+
+        if self.addresses.is_empty() {
+            return true;
+        }
+
+        match payload {
+            Payload::OwnershipReport(report) | Payload::TrafficReport(report) => {
+                self.addresses.contains(&report.participant_address)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn parse_number<T: TryFrom<u64>>(value: &str) -> Result<T, String> {
+    let parsed = match value.strip_prefix("0x").or(value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+
+    parsed
+        .ok()
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or_else(|| format!("Invalid number {value}"))
+}
+
+/// Payload in engineering units, as its name followed by `field=value` pairs
+fn describe(payload: &Payload) -> serde_json::Result<String> {
+    match payload {
+        Payload::OwnershipReport(report) => return Ok(describe_report("OwnershipReport", report)),
+        Payload::TrafficReport(report) => return Ok(describe_report("TrafficReport", report)),
+        _ => {}
+    }
+
+    // The serialised view, unwrapping the enum variant names into the description
+    let mut names = vec![];
+    let mut value = serde_json::to_value(payload)?;
+    while let Value::Object(fields) = &mut value {
+        let Some(name) = fields
+            .keys()
+            .next()
+            .filter(|name| fields.len() == 1 && name.starts_with(char::is_uppercase))
+            .cloned()
+        else {
+            break;
+        };
+
+        let inner = fields.remove(&name).unwrap_or_default();
+        names.push(name);
+        value = inner;
+    }
+
+    names.push(format_value(&value));
+    Ok(names.join(" "))
+}
+
+fn describe_report(name: &str, report: &Report) -> String {
+    let altitude = if report.is_altitude_valid() {
+        format!("{}ft", report.get_altitude())
+    } else {
+        "-".to_owned()
+    };
+    let speed = report
+        .get_horizontal_velocity()
+        .map_or("-".to_owned(), |speed| format!("{speed}kt"));
+    let vertical_speed = report
+        .get_vertical_velocity()
+        .map_or("-".to_owned(), |speed| format!("{speed:+}fpm"));
+
+    format!(
+        "{name} {:06X} {:?} callsign={} lat={:.5} lon={:.5} alt={altitude} track={:.1} \
+         speed={speed} vs={vertical_speed} alert={:?} emitter={:?} emergency={:?}",
+        report.participant_address,
+        report.get_address_type(),
+        report.get_callsign().unwrap_or("?").trim_end(),
+        report.get_latitude(),
+        report.get_longitude(),
+        report.get_track_heading(),
+        report.get_traffic_alert_status(),
+        report.get_emitter_category(),
+        report.get_emergency_priority_code(),
+    )
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_owned(),
+        Value::String(value) => value.clone(),
+        Value::Number(number) if number.is_f64() => {
+            let value = format!("{:.5}", number.as_f64().unwrap_or_default());
+            value.trim_end_matches('0').trim_end_matches('.').to_owned()
+        }
+        // Byte arrays, such as uplink payloads, in hex
+        Value::Array(values)
+            if values
+                .iter()
+                .all(|value| value.as_u64().is_some_and(|value| value <= 0xFF)) =>
+        {
+            values
+                .iter()
+                .map(|value| format!("{:02X}", value.as_u64().unwrap_or_default()))
+                .collect()
+        }
+        Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(format_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::Object(_) => format!("{key}=({})", format_value(value)),
+                _ => format!("{key}={}", format_value(value)),
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        value => value.to_string(),
+    }
+}
+
+fn dump(options: &Options, framer: &mut Gdl90Framer, out: &mut impl Write) -> io::Result<()> {
+    for result in framer {
+        let message = match result {
+            Ok(message) => message,
+            Err(err) => {
+                eprintln!("Invalid frame: {err:?}");
+                continue;
+            }
+        };
+
+        let message_id = message.get_message_id();
+        let payload = match message.get_payload() {
+            Ok(payload) => payload,
+            Err(err) => {
+                eprintln!("Invalid payload for message ID {message_id:#04x}: {err:?}");
+                continue;
+            }
+        };
+
+        if !options.matches(message_id, &payload) {
+            continue;
+        }
+
+        if options.json {
+            serde_json::to_writer(&mut *out, &payload)?;
+            writeln!(out)?;
+        } else {
+            writeln!(out, "{message_id:#04x} {}", describe(&payload)?)?;
+        }
+    }
+
+    out.flush()
+}
+
+fn run(options: &Options) -> io::Result<()> {
+    let mut framer = Gdl90Framer::new();
+    let mut out = io::stdout().lock();
+
+    if let Source::Udp(port) = options.source {
+        let socket = UdpSocket::bind(("0.0.0.0", port))?;
+        let mut buffer = [0; 2048];
+
+        loop {
+            let (len, _) = socket.recv_from(&mut buffer)?;
+            framer.push(&buffer[..len]);
+            dump(options, &mut framer, &mut out)?;
+        }
+    }
+
+    let mut input: Box<dyn Read> = match &options.source {
+        Source::File(path) => Box::new(File::open(path)?),
+        _ => Box::new(io::stdin().lock()),
+    };
+    let mut buffer = [0; 4096];
+
+    loop {
+        let len = input.read(&mut buffer)?;
+        if len == 0 {
+            return Ok(());
+        }
+
+        framer.push(&buffer[..len]);
+        dump(options, &mut framer, &mut out)?;
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{dump, Options, Source};
+    use gdl90codec::{
+        message::{create_message, Gdl90Framer},
+        payload::Payload,
+        report::{AddressType, Report},
+    };
+
+    const HEARTBEAT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8B\x7E";
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]).unwrap(), Some(Options::default()));
+        assert_eq!(parse(&["--help"]).unwrap(), None);
+        assert_eq!(parse(&["-u"]).unwrap().unwrap().source, Source::Udp(4000));
+        assert_eq!(
+            parse(&["--udp", "43211", "-j"]).unwrap(),
+            Some(Options {
+                source: Source::Udp(43211),
+                json: true,
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            parse(&["-m", "0x14", "-m", "10", "-a", "0xABCDEF", "capture.bin"]).unwrap(),
+            Some(Options {
+                source: Source::File("capture.bin".to_owned()),
+                message_ids: vec![0x14, 10],
+                addresses: vec![0xABCDEF],
+                ..Default::default()
+            })
+        );
+        assert!(parse(&["-m", "256"]).is_err());
+        assert!(parse(&["--unknown"]).is_err());
+    }
+
+    #[test]
+    fn test_dump() {
+        let mut framer = Gdl90Framer::new();
+        let mut out = vec![];
+
+        framer.push(HEARTBEAT);
+        let options = parse(&["-j"]).unwrap().unwrap();
+        dump(&options, &mut framer, &mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("{\"HeartBeat\":{"));
+
+        let mut out = vec![];
+        framer.push(HEARTBEAT);
+        let options = parse(&["-m", "10"]).unwrap().unwrap();
+        dump(&options, &mut framer, &mut out).unwrap();
+        assert!(out.is_empty());
+
+        framer.push(HEARTBEAT);
+        let options = parse(&["-a", "1"]).unwrap().unwrap();
+        dump(&options, &mut framer, &mut out).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_dump_readable() {
+        let mut report = Report::default();
+        report.participant_address = 0xABCDEF;
+        report.set_address_type(AddressType::ADSBWithICAOAddress);
+        report.set_callsign(*b"N825V   ");
+        report.set_latitude(45.);
+        report.set_longitude(-135.);
+        report.set_altitude(5000);
+        report.set_horizontal_velocity(Some(120));
+        report.set_vertical_velocity(Some(-10)); // 64fpm units
+
+        let mut framer = Gdl90Framer::new();
+        let message = create_message(&Payload::TrafficReport(report)).unwrap();
+        framer.push(&message.encode());
+        framer.push(HEARTBEAT);
+
+        let mut out = vec![];
+        let options = parse(&[]).unwrap().unwrap();
+        dump(&options, &mut framer, &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(
+            "0x14 TrafficReport ABCDEF ADSBWithICAOAddress callsign=N825V \
+             lat=45.00000 lon=-135.00000 alt=5000ft track=0.0 speed=120kt vs=-640fpm"
+        ));
+        assert!(lines[1].starts_with("0x00 HeartBeat "));
+        assert!(lines[1].contains(" gps_pos_valid=true "));
+    }
+}
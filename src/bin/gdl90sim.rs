@@ -0,0 +1,111 @@
+//! Send simulated ownship and traffic from a scenario file to a UDP destination
+//!
+//! Requires the `cli` feature: `cargo run --features cli --bin gdl90sim -- --help`
+use gdl90codec::simulator::{Scenario, Simulator};
+use std::{env, fs, process::ExitCode};
+
+const USAGE: &str = "Usage: gdl90sim [OPTIONS] SCENARIO
+
+Send the heartbeat, ownship and traffic messages described by the SCENARIO
+JSON file to a UDP destination, until interrupted.
+
+Options:
+  -d, --destination HOST:PORT   UDP destination (default 127.0.0.1:4000),
+                                may be a broadcast address
+  -h, --help                    Print this help";
+
+const DEFAULT_DESTINATION: &str = "127.0.0.1:4000";
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    scenario: String,
+    destination: String,
+}
+
+impl Options {
+    /// Parse the command line arguments, `None` if help was requested
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut scenario = None;
+        let mut destination = DEFAULT_DESTINATION.to_owned();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-d" | "--destination" => {
+                    destination = args.next().ok_or("Missing destination")?;
+                }
+                arg if arg.starts_with('-') => return Err(format!("Unknown option {arg}")),
+                path => scenario = Some(path.to_owned()),
+            }
+        }
+
+        Ok(Some(Options {
+            scenario: scenario.ok_or("Missing scenario")?,
+            destination,
+        }))
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let scenario = fs::read_to_string(&options.scenario)
+        .map_err(|err| format!("Cannot read {}: {err}", options.scenario))?;
+    let scenario = Scenario::from_json(&scenario)
+        .map_err(|err| format!("Invalid scenario {}: {err:?}", options.scenario))?;
+
+    Simulator::new(scenario)
+        .run(options.destination.as_str())
+        .map_err(|err| format!("Cannot send to {}: {err:?}", options.destination))
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&["-h"]).unwrap(), None);
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["-d"]).is_err());
+        assert_eq!(
+            parse(&["scenario.json"]).unwrap(),
+            Some(Options {
+                scenario: "scenario.json".to_owned(),
+                destination: "127.0.0.1:4000".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse(&["--destination", "192.168.10.255:4000", "scenario.json"]).unwrap(),
+            Some(Options {
+                scenario: "scenario.json".to_owned(),
+                destination: "192.168.10.255:4000".to_owned(),
+            })
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Tokio codec for GDL90 streams (requires the `async` feature)
+//!
+//! [GDL90Codec] can be used with `FramedRead` over a serial port, or `UdpFramed` over a
+//! UDP socket, to yield decoded [Payload] values. A frame that fails to decode is
+//! returned as an inner error so that a single corrupt frame does not terminate the
+//! stream.
+use crate::{
+    error::GDL90Error,
+    message::{create_message, decode_frame, GDL90Message},
+    payload::Payload,
+    registry::PayloadRegistry,
+};
+use bytes::{Buf, BufMut, BytesMut};
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Decodes frames into [Payload] values and encodes [Payload] or [GDL90Message] values
+#[derive(Debug, Default)]
+pub struct GDL90Codec {
+    registry: Option<Arc<PayloadRegistry>>,
+}
+
+impl GDL90Codec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Decode vendor specific message IDs with the decoders in `registry`
+    pub fn with_registry(registry: Arc<PayloadRegistry>) -> Self {
+        Self {
+            registry: Some(registry),
+        }
+    }
+
+    fn get_payload(&self, message: GDL90Message) -> Result<Payload, GDL90Error> {
+        match &self.registry {
+            Some(registry) => message.get_payload_with_registry(registry),
+            None => message.get_payload(),
+        }
+    }
+}
+
+impl Decoder for GDL90Codec {
+    type Item = Result<Payload, GDL90Error>;
+    type Error = GDL90Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            let (consumed, result) = decode_frame(src);
+            src.advance(consumed);
+
+            match result {
+                Some(message) => {
+                    return Ok(Some(message.and_then(|m| self.get_payload(m))));
+                }
+                None if consumed == 0 => return Ok(None),
+                None => {}
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let result = self.decode(src)?;
+        if result.is_none() {
+            // Trailing flag or an incomplete frame
+            src.clear();
+        }
+        Ok(result)
+    }
+}
+
+impl Encoder<Payload> for GDL90Codec {
+    type Error = GDL90Error;
+
+    fn encode(&mut self, item: Payload, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.encode(create_message(&item)?, dst)
+    }
+}
+
+impl Encoder<GDL90Message> for GDL90Codec {
+    type Error = GDL90Error;
+
+    fn encode(&mut self, item: GDL90Message, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.put_slice(&item.encode());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GDL90Codec;
+    use crate::{error::GDL90Error, payload::Payload};
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    const HEARTBEAT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8B\x7E";
+
+    #[test]
+    fn test_decode_partial_frames() {
+        let mut codec = GDL90Codec::new();
+        let mut buffer = BytesMut::new();
+
+        buffer.extend_from_slice(&HEARTBEAT[..5]);
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+
+        buffer.extend_from_slice(&HEARTBEAT[5..]);
+        buffer.extend_from_slice(&[0x7E, 0x00, 0x01]);
+        assert!(matches!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(Ok(Payload::HeartBeat(_)))
+        ));
+        assert!(codec.decode(&mut buffer).unwrap().is_none());
+        assert!(codec.decode_eof(&mut buffer).unwrap().is_none());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_checksum_error() {
+        let mut codec = GDL90Codec::new();
+        let mut buffer = BytesMut::from(&[0x7E, 0x00, 0x81, 0x41, 0xDB, 0x00, 0x00, 0x7E][..]);
+        buffer.extend_from_slice(HEARTBEAT);
+
+        assert!(matches!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(Err(GDL90Error::ChecksumMismatch(_, _)))
+        ));
+        assert!(matches!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(Ok(Payload::HeartBeat(_)))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut codec = GDL90Codec::new();
+        let mut buffer = BytesMut::from(HEARTBEAT);
+
+        let payload = codec.decode_eof(&mut buffer).unwrap().unwrap().unwrap();
+
+        let mut encoded = BytesMut::new();
+        codec.encode(payload, &mut encoded).unwrap();
+        assert_eq!(&encoded[..], HEARTBEAT);
+    }
+}
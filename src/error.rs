@@ -1,6 +1,9 @@
 //! Maps [deku::error::DekuError] errors and other errors.
 use deku::DekuError;
-use std::{borrow::Cow, io::ErrorKind};
+use std::{
+    borrow::Cow,
+    io::{self, ErrorKind, Read},
+};
 
 #[derive(Debug)]
 pub enum GDL90Error {
@@ -43,3 +46,26 @@ impl From<DekuError> for GDL90Error {
         }
     }
 }
+
+impl From<io::Error> for GDL90Error {
+    fn from(value: io::Error) -> Self {
+        GDL90Error::Io(value.kind())
+    }
+}
+
+/// Fill `buffer`, false if the reader is already at the end
+pub(crate) fn read_or_eof(reader: &mut impl Read, buffer: &mut [u8]) -> Result<bool, GDL90Error> {
+    let mut filled = 0;
+
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(GDL90Error::Io(ErrorKind::UnexpectedEof)),
+            Ok(len) => filled += len,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(true)
+}
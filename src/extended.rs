@@ -1,12 +1,21 @@
 //! Extended specification for message ID 0x65
 //!
 //! Reference: <https://www.foreflight.com/connect/spec/>
+use crate::error::GDL90Error;
 use deku::prelude::*;
-use std::str::{from_utf8, Utf8Error};
+use enum_ordinalize::Ordinalize;
+use std::{
+    iter::once,
+    str::{from_utf8, Utf8Error},
+};
 
 const ID_SUB_ID: u8 = 0;
 const AHRS_SUB_ID: u8 = 1;
 
+const AHRS_ANGLE_INVALID: i16 = 0x7FFF;
+const AHRS_HEADING_INVALID: u16 = 0x7FFF;
+const AHRS_AIRSPEED_INVALID: u16 = 0xFFFF;
+
 /// ID sub message (ID 0x00)
 #[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
 #[deku(bit_order = "msb", endian = "big")]
@@ -16,39 +25,90 @@ pub struct IDMessage {
     pub device_serial_number: u64,
     device_name: [u8; 8],
     device_long_name: [u8; 16],
-
-    #[deku(bits = "1")]
-    geometric_altitude_datum: u8,
-
-    #[deku(bits = "2")]
-    internet_policy: u8,
-
-    #[deku(bits = "29")]
-    unused: u32,
+    capabilities: u32,
 }
 
 impl IDMessage {
     pub fn get_device_name(&self) -> Result<String, Utf8Error> {
-        let mut end_idx = self.device_name.len();
-        for (idx, b) in self.device_name.iter().enumerate() {
-            if *b == 0 {
-                end_idx = idx;
-                break;
-            }
-        }
-        from_utf8(&self.device_name[0..end_idx]).map(|s| s.to_owned())
+        get_null_terminated(&self.device_name)
+    }
+
+    /// Device name (at most 8 bytes of UTF-8)
+    pub fn set_device_name(&mut self, value: &str) -> Result<(), GDL90Error> {
+        set_null_terminated(&mut self.device_name, value)
     }
 
     pub fn get_device_long_name(&self) -> Result<String, Utf8Error> {
-        let mut end_idx = self.device_name.len();
-        for (idx, b) in self.device_name.iter().enumerate() {
-            if *b == 0 {
-                end_idx = idx;
-                break;
-            }
-        }
-        from_utf8(&self.device_long_name[0..end_idx]).map(|s| s.to_owned())
+        get_null_terminated(&self.device_long_name)
+    }
+
+    /// Device long name (at most 16 bytes of UTF-8)
+    pub fn set_device_long_name(&mut self, value: &str) -> Result<(), GDL90Error> {
+        set_null_terminated(&mut self.device_long_name, value)
+    }
+
+    /// Raw capabilities mask, including reserved bits
+    pub fn get_capabilities(&self) -> u32 {
+        self.capabilities
+    }
+
+    /// Raw capabilities mask, including reserved bits
+    pub fn set_capabilities(&mut self, value: u32) {
+        self.capabilities = value;
+    }
+
+    /// Datum of the ownship geometric altitude (capabilities bit 0)
+    pub fn get_geometric_altitude_datum(&self) -> GeometricAltitudeDatum {
+        GeometricAltitudeDatum::VARIANTS[(self.capabilities & 0x1) as usize]
+    }
+
+    /// Datum of the ownship geometric altitude (capabilities bit 0)
+    pub fn set_geometric_altitude_datum(&mut self, value: GeometricAltitudeDatum) {
+        self.capabilities = (self.capabilities & !0x1) | value.ordinal() as u32;
+    }
+
+    /// Internet access policy (capabilities bits 1 - 2)
+    pub fn get_internet_policy(&self) -> InternetPolicy {
+        InternetPolicy::VARIANTS[((self.capabilities >> 1) & 0x3) as usize]
+    }
+
+    /// Internet access policy (capabilities bits 1 - 2)
+    pub fn set_internet_policy(&mut self, value: InternetPolicy) {
+        self.capabilities = (self.capabilities & !0x6) | (value.ordinal() as u32) << 1;
+    }
+}
+
+fn get_null_terminated(value: &[u8]) -> Result<String, Utf8Error> {
+    let end_idx = value.iter().position(|b| *b == 0).unwrap_or(value.len());
+    from_utf8(&value[0..end_idx]).map(|s| s.to_owned())
+}
+
+fn set_null_terminated(field: &mut [u8], value: &str) -> Result<(), GDL90Error> {
+    if value.len() > field.len() {
+        return Err(GDL90Error::InvalidParam(
+            format!("Name is longer than {} bytes", field.len()).into(),
+        ));
     }
+
+    field.fill(0);
+    field[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(())
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeometricAltitudeDatum {
+    WGS84Ellipsoid,
+    MSL,
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InternetPolicy {
+    Unrestricted,
+    Expensive,
+    Disallowed,
+    Reserved,
 }
 
 /// Attitude and heading reference system (AHRS) sub message (ID 0x01)
@@ -70,11 +130,112 @@ pub struct AHRS {
     true_airspeed: u16,
 }
 
+impl AHRS {
+    /// Roll (in degrees, right wing down positive)
+    pub fn get_roll(&self) -> Option<f32> {
+        match self.roll {
+            AHRS_ANGLE_INVALID => None,
+            value => Some(value as f32 / 10.),
+        }
+    }
+
+    /// Roll (in degrees, right wing down positive, -180 to 180)
+    pub fn set_roll(&mut self, value: Option<f32>) {
+        self.roll = value.map_or(AHRS_ANGLE_INVALID, |value| {
+            (value.clamp(-180., 180.) * 10.).round() as i16
+        });
+    }
+
+    /// Pitch (in degrees, nose up positive)
+    pub fn get_pitch(&self) -> Option<f32> {
+        match self.pitch {
+            AHRS_ANGLE_INVALID => None,
+            value => Some(value as f32 / 10.),
+        }
+    }
+
+    /// Pitch (in degrees, nose up positive, -180 to 180)
+    pub fn set_pitch(&mut self, value: Option<f32>) {
+        self.pitch = value.map_or(AHRS_ANGLE_INVALID, |value| {
+            (value.clamp(-180., 180.) * 10.).round() as i16
+        });
+    }
+
+    pub fn get_heading_type(&self) -> AHRSHeadingType {
+        AHRSHeadingType::VARIANTS[self.heading_type as usize]
+    }
+
+    pub fn set_heading_type(&mut self, value: AHRSHeadingType) {
+        self.heading_type = value.ordinal() as u8;
+    }
+
+    /// Heading (in degrees)
+    pub fn get_heading(&self) -> Option<f32> {
+        match self.heading {
+            AHRS_HEADING_INVALID => None,
+            // 15 bit two's complement
+            value => Some((((value << 1) as i16) >> 1) as f32 / 10.),
+        }
+    }
+
+    /// Heading (in degrees, normalised to 0 to 360)
+    ///
+    /// Headings are encoded as positive values since -0.1 would encode as the invalid
+    /// value. An invalid heading is encoded as 0xFFFF, which also sets the heading type to
+    /// magnetic.
+    pub fn set_heading(&mut self, value: Option<f32>) {
+        if let Some(value) = value {
+            self.heading = ((value * 10.).round() as i32).rem_euclid(3600) as u16;
+        } else {
+            self.heading_type = AHRSHeadingType::Magnetic.ordinal() as u8;
+            self.heading = AHRS_HEADING_INVALID;
+        }
+    }
+
+    /// Indicated airspeed (in knots)
+    pub fn get_indicated_airspeed(&self) -> Option<u16> {
+        match self.indicated_airspeed {
+            AHRS_AIRSPEED_INVALID => None,
+            value => Some(value),
+        }
+    }
+
+    /// Indicated airspeed (in knots)
+    pub fn set_indicated_airspeed(&mut self, value: Option<u16>) {
+        self.indicated_airspeed = value.map_or(AHRS_AIRSPEED_INVALID, |value| {
+            value.min(AHRS_AIRSPEED_INVALID - 1)
+        });
+    }
+
+    /// True airspeed (in knots)
+    pub fn get_true_airspeed(&self) -> Option<u16> {
+        match self.true_airspeed {
+            AHRS_AIRSPEED_INVALID => None,
+            value => Some(value),
+        }
+    }
+
+    /// True airspeed (in knots)
+    pub fn set_true_airspeed(&mut self, value: Option<u16>) {
+        self.true_airspeed = value.map_or(AHRS_AIRSPEED_INVALID, |value| {
+            value.min(AHRS_AIRSPEED_INVALID - 1)
+        });
+    }
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AHRSHeadingType {
+    True,
+    Magnetic,
+}
+
 /// Sub message container enum
 #[derive(Debug)]
 pub enum ExtendedX65SubMessage {
     AHRS(AHRS),
     IDMessage(IDMessage),
+    Unknown(u8, Vec<u8>),
 }
 
 impl ExtendedX65SubMessage {
@@ -86,6 +247,9 @@ impl ExtendedX65SubMessage {
             ExtendedX65SubMessage::AHRS(payload) => {
                 Ok(set_sub_id(payload.to_bytes()?, AHRS_SUB_ID))
             }
+            ExtendedX65SubMessage::Unknown(sub_id, data) => {
+                Ok(once(*sub_id).chain(data.iter().copied()).collect())
+            }
         }
     }
 }
@@ -98,9 +262,7 @@ impl TryFrom<&[u8]> for ExtendedX65SubMessage {
             match *submsgid {
                 ID_SUB_ID => parse_id_message(input),
                 AHRS_SUB_ID => parse_ahrs_message(input),
-                id => Err(DekuError::Parse(
-                    format!("Unknown sub message id {id}").into(),
-                ))?,
+                id => Ok(ExtendedX65SubMessage::Unknown(id, input[1..].to_vec())),
             }
         } else {
             Err(DekuError::Parse(
@@ -127,7 +289,10 @@ fn parse_ahrs_message(input: &[u8]) -> Result<ExtendedX65SubMessage, DekuError>
 
 #[cfg(test)]
 mod test {
-    use super::ExtendedX65SubMessage;
+    use super::{
+        AHRSHeadingType, ExtendedX65SubMessage, GeometricAltitudeDatum, IDMessage, InternetPolicy,
+        AHRS,
+    };
 
     #[test]
     fn test_parse_id() {
@@ -143,8 +308,169 @@ mod test {
             assert_eq!(payload.device_serial_number, 0x04_03_02_01); // Fake serial number
             assert_eq!(payload.get_device_name().unwrap(), "SkyEcho");
             assert_eq!(payload.get_device_long_name().unwrap(), "SkyEcho");
+            assert_eq!(
+                payload.get_geometric_altitude_datum(),
+                GeometricAltitudeDatum::WGS84Ellipsoid
+            );
+            assert_eq!(payload.get_internet_policy(), InternetPolicy::Unrestricted);
+        } else {
+            panic!()
+        }
+    }
+
+    #[test]
+    fn test_encode_id() {
+        let data = [
+            0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x04, 0x03, 0x02, 0x01, 0x53, 0x6B, 0x79, 0x45,
+            0x63, 0x68, 0x6F, 0x00, 0x53, 0x6B, 0x79, 0x45, 0x63, 0x68, 0x6F, 0x20, 0x55, 0x41,
+            0x54, 0x20, 0x49, 0x49, 0x49, 0x49, 0x00, 0x00, 0x00, 0x03,
+        ];
+
+        let id = IDMessage::try_from(&data[..]).unwrap();
+        assert_eq!(id.get_device_long_name().unwrap(), "SkyEcho UAT IIII");
+        assert_eq!(
+            id.get_geometric_altitude_datum(),
+            GeometricAltitudeDatum::MSL
+        );
+        assert_eq!(id.get_internet_policy(), InternetPolicy::Expensive);
+        assert_eq!(id.get_capabilities(), 0x03);
+
+        let mut new_id = IDMessage {
+            version: 1,
+            device_serial_number: 0x04_03_02_01,
+            ..Default::default()
+        };
+        new_id.set_device_name("SkyEcho").unwrap();
+        new_id.set_device_long_name("SkyEcho UAT IIII").unwrap();
+        new_id.set_geometric_altitude_datum(GeometricAltitudeDatum::MSL);
+        new_id.set_internet_policy(InternetPolicy::Expensive);
+
+        assert_eq!(id, new_id);
+
+        let encoded = ExtendedX65SubMessage::IDMessage(new_id).to_bytes().unwrap();
+        assert_eq!(&encoded[..], &data[..]);
+
+        let mut id = IDMessage::default();
+        assert!(id.set_device_name("Too long name").is_err());
+        assert!(id.set_device_long_name("Sixteen bytes ok").is_ok());
+    }
+
+    #[test]
+    fn test_parse_ahrs() {
+        let data = [
+            0x01, 0x00, 0x64, 0xFF, 0x9C, 0x83, 0x84, 0x00, 0x78, 0xFF, 0xFF,
+        ];
+
+        if let Ok(ExtendedX65SubMessage::AHRS(ref payload)) =
+            ExtendedX65SubMessage::try_from(&data[..])
+        {
+            assert_eq!(payload.get_roll(), Some(10.));
+            assert_eq!(payload.get_pitch(), Some(-10.));
+            assert_eq!(payload.get_heading_type(), AHRSHeadingType::Magnetic);
+            assert_eq!(payload.get_heading(), Some(90.));
+            assert_eq!(payload.get_indicated_airspeed(), Some(120));
+            assert_eq!(payload.get_true_airspeed(), None);
         } else {
             panic!()
         }
+
+        let mut ahrs = AHRS::default();
+        ahrs.set_roll(Some(10.));
+        ahrs.set_pitch(Some(-10.));
+        ahrs.set_heading_type(AHRSHeadingType::Magnetic);
+        ahrs.set_heading(Some(90.));
+        ahrs.set_indicated_airspeed(Some(120));
+        ahrs.set_true_airspeed(None);
+
+        let encoded = ExtendedX65SubMessage::AHRS(ahrs).to_bytes().unwrap();
+        assert_eq!(&encoded[..], &data[..]);
+    }
+
+    #[test]
+    fn test_ahrs_invalid_and_negative_heading() {
+        // Invalid roll and pitch are 0x7FFF, invalid heading and airspeeds 0xFFFF
+        let data = [
+            0x01, 0x7F, 0xFF, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+
+        let ahrs = AHRS::try_from(&data[..]).unwrap();
+        assert_eq!(ahrs.get_roll(), None);
+        assert_eq!(ahrs.get_pitch(), None);
+        assert_eq!(ahrs.get_heading(), None);
+        assert_eq!(ahrs.get_indicated_airspeed(), None);
+        assert_eq!(ahrs.get_true_airspeed(), None);
+
+        let mut invalid = AHRS::default();
+        invalid.set_roll(None);
+        invalid.set_pitch(None);
+        invalid.set_heading(None);
+        invalid.set_indicated_airspeed(None);
+        invalid.set_true_airspeed(None);
+
+        let encoded = ExtendedX65SubMessage::AHRS(invalid).to_bytes().unwrap();
+        assert_eq!(&encoded[..], &data[..]);
+
+        // Negative headings decode as sent, -360.0 and -0.2
+        let ahrs = AHRS::try_from(&[0x01, 0, 0, 0, 0, 0x71, 0xF0, 0, 0, 0, 0][..]).unwrap();
+        assert_eq!(ahrs.get_heading(), Some(-360.));
+        let ahrs = AHRS::try_from(&[0x01, 0, 0, 0, 0, 0x7F, 0xFE, 0, 0, 0, 0][..]).unwrap();
+        assert_eq!(ahrs.get_heading(), Some(-0.2));
+
+        // and are encoded from 0 to 360, keeping clear of the invalid value
+        let mut ahrs = AHRS::default();
+        ahrs.set_heading(Some(-45.5));
+        assert_eq!(ahrs.get_heading(), Some(314.5));
+        assert_eq!(ahrs.get_heading_type(), AHRSHeadingType::True);
+        ahrs.set_heading(Some(-0.1));
+        assert_eq!(ahrs.get_heading(), Some(359.9));
+        ahrs.set_heading(Some(-0.04));
+        assert_eq!(ahrs.get_heading(), Some(0.));
+        ahrs.set_heading(Some(359.96));
+        assert_eq!(ahrs.get_heading(), Some(0.));
+        ahrs.set_heading(Some(-360.));
+        assert_eq!(ahrs.get_heading(), Some(0.));
+    }
+
+    #[test]
+    fn test_ahrs_spec_limits() {
+        // Roll -180.0 (0xF8F8), pitch 180.0 (0x0708), magnetic heading 359.9
+        // (0x8000 | 0x0E0F), IAS 0 and TAS 0xFFFE knots
+        let data = [
+            0x01, 0xF8, 0xF8, 0x07, 0x08, 0x8E, 0x0F, 0x00, 0x00, 0xFF, 0xFE,
+        ];
+
+        let ahrs = AHRS::try_from(&data[..]).unwrap();
+        assert_eq!(ahrs.get_roll(), Some(-180.));
+        assert_eq!(ahrs.get_pitch(), Some(180.));
+        assert_eq!(ahrs.get_heading_type(), AHRSHeadingType::Magnetic);
+        assert_eq!(ahrs.get_heading(), Some(359.9));
+        assert_eq!(ahrs.get_indicated_airspeed(), Some(0));
+        assert_eq!(ahrs.get_true_airspeed(), Some(0xFFFE));
+
+        let mut new_ahrs = AHRS {
+            sub_id: 0x01,
+            ..Default::default()
+        };
+        new_ahrs.set_roll(Some(-200.));
+        new_ahrs.set_pitch(Some(180.));
+        new_ahrs.set_heading_type(AHRSHeadingType::Magnetic);
+        new_ahrs.set_heading(Some(359.9));
+        new_ahrs.set_indicated_airspeed(Some(0));
+        new_ahrs.set_true_airspeed(Some(0xFFFF));
+        assert_eq!(ahrs, new_ahrs);
+    }
+
+    #[test]
+    fn test_unknown_sub_message() {
+        let data = [0x7F, 0x01, 0x02, 0x03];
+
+        let message = ExtendedX65SubMessage::try_from(&data[..]).unwrap();
+        assert!(matches!(
+            &message,
+            ExtendedX65SubMessage::Unknown(0x7F, rest) if rest == &[0x01, 0x02, 0x03]
+        ));
+        assert_eq!(&message.to_bytes().unwrap()[..], &data[..]);
+
+        assert!(ExtendedX65SubMessage::try_from(&[][..]).is_err());
     }
 }
@@ -0,0 +1,422 @@
+//! FIS-B information frames and application protocol data units (APDUs)
+//!
+//! The application data of an [UplinkData](crate::uplink::UplinkData) message is a
+//! sequence of length-prefixed information frames, most of which carry a FIS-B APDU.
+//!
+//! Reference: DO-282B / DO-358
+use crate::error::GDL90Error;
+use deku::prelude::*;
+use enum_ordinalize::Ordinalize;
+
+pub mod nexrad;
+pub mod overlay;
+pub mod text;
+
+/// Iterator over the information frames in uplink application data
+#[derive(Debug, Clone)]
+pub struct InformationFrames<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> InformationFrames<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+}
+
+impl Iterator for InformationFrames<'_> {
+    type Item = Result<InformationFrame, GDL90Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The application data is zero padded after the last frame
+        if self.data.len() < 2 || self.data[..2] == [0, 0] {
+            return None;
+        }
+
+        match InformationFrame::from_bytes((self.data, 0)) {
+            Ok(((rest, _), frame)) => {
+                self.data = rest;
+                Some(Ok(frame))
+            }
+            Err(err) => {
+                self.data = &[];
+                Some(Err(err.into()))
+            }
+        }
+    }
+}
+
+/// Largest information frame data, limited by the 9-bit length field
+const MAX_FRAME_LENGTH: usize = 511;
+
+/// Information frame
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct InformationFrame {
+    #[deku(bits = "9", update = "self.data.len()")]
+    length: u16,
+
+    #[deku(bits = "3")]
+    reserved: u8,
+
+    #[deku(bits = "4")]
+    frame_type: u8,
+
+    #[deku(count = "length")]
+    data: Vec<u8>,
+}
+
+impl InformationFrame {
+    pub fn get_frame_type(&self) -> FrameType {
+        FrameType::VARIANTS[self.frame_type as usize]
+    }
+
+    pub fn set_frame_type(&mut self, value: FrameType) {
+        self.frame_type = value.ordinal() as u8;
+    }
+
+    /// Frame data (at most 511 bytes)
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Frame data (at most 511 bytes)
+    pub fn set_data(&mut self, value: Vec<u8>) -> Result<(), GDL90Error> {
+        if value.len() > MAX_FRAME_LENGTH {
+            return Err(GDL90Error::InvalidParam(
+                format!("Frame data of {} bytes is too long", value.len()).into(),
+            ));
+        }
+
+        self.length = value.len() as u16;
+        self.data = value;
+        Ok(())
+    }
+
+    /// Decode the frame data as a FIS-B APDU
+    pub fn get_apdu(&self) -> Result<Apdu, GDL90Error> {
+        match self.get_frame_type() {
+            FrameType::FisbApdu => Apdu::try_from(&self.data[..]),
+            frame_type => Err(GDL90Error::Parse(
+                format!("Frame type {frame_type:?} does not contain an APDU").into(),
+            )),
+        }
+    }
+}
+
+/// FIS-B application protocol data unit
+#[derive(Debug, PartialEq)]
+pub struct Apdu {
+    header: ApduHeader,
+    payload: Vec<u8>,
+}
+
+impl Apdu {
+    pub fn get_header(&self) -> &ApduHeader {
+        &self.header
+    }
+
+    /// Product specific payload following the header
+    pub fn get_payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl TryFrom<&[u8]> for Apdu {
+    type Error = GDL90Error;
+
+    fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+        let ((rest, bit_offset), header) = ApduHeader::from_bytes((input, 0))?;
+
+        // The header is padded to a whole number of bytes
+        let rest = if bit_offset > 0 { &rest[1..] } else { rest };
+
+        Ok(Apdu {
+            header,
+            payload: rest.to_vec(),
+        })
+    }
+}
+
+/// FIS-B APDU header
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct ApduHeader {
+    #[deku(bits = "1")]
+    a_flag: bool,
+
+    #[deku(bits = "1")]
+    g_flag: bool,
+
+    #[deku(bits = "1")]
+    p_flag: bool,
+
+    #[deku(bits = "11")]
+    product_id: u16,
+
+    #[deku(bits = "1")]
+    s_flag: bool,
+
+    #[deku(bits = "2")]
+    time_option: u8,
+
+    #[deku(bits = "4", cond = "*time_option & 0b10 != 0")]
+    month: Option<u8>,
+
+    #[deku(bits = "5", cond = "*time_option & 0b10 != 0")]
+    day: Option<u8>,
+
+    #[deku(bits = "5")]
+    hours: u8,
+
+    #[deku(bits = "6")]
+    minutes: u8,
+
+    #[deku(bits = "6", cond = "*time_option & 0b01 != 0")]
+    seconds: Option<u8>,
+
+    #[deku(bits = "10", cond = "*s_flag")]
+    product_file_id: Option<u16>,
+
+    #[deku(bits = "9", cond = "*s_flag")]
+    product_file_length: Option<u16>,
+
+    #[deku(bits = "9", cond = "*s_flag")]
+    apdu_number: Option<u16>,
+}
+
+impl ApduHeader {
+    /// Application method flag
+    pub fn get_a_flag(&self) -> bool {
+        self.a_flag
+    }
+
+    /// Geographic location flag
+    pub fn get_g_flag(&self) -> bool {
+        self.g_flag
+    }
+
+    /// Product descriptor option flag
+    pub fn get_p_flag(&self) -> bool {
+        self.p_flag
+    }
+
+    /// FIS-B product ID
+    pub fn get_product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    /// Segmentation flag
+    pub fn get_s_flag(&self) -> bool {
+        self.s_flag
+    }
+
+    /// Product time (month, 1 - 12), if present
+    pub fn get_month(&self) -> Option<u8> {
+        self.month
+    }
+
+    /// Product time (day of month), if present
+    pub fn get_day(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// Product time (hours)
+    pub fn get_hours(&self) -> u8 {
+        self.hours
+    }
+
+    /// Product time (minutes)
+    pub fn get_minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    /// Product time (seconds), if present
+    pub fn get_seconds(&self) -> Option<u8> {
+        self.seconds
+    }
+
+    /// Segmentation fields, present if the segmentation flag is set
+    pub fn get_segmentation(&self) -> Option<ApduSegmentation> {
+        match (
+            self.product_file_id,
+            self.product_file_length,
+            self.apdu_number,
+        ) {
+            (Some(product_file_id), Some(product_file_length), Some(apdu_number)) => {
+                Some(ApduSegmentation {
+                    product_file_id,
+                    product_file_length,
+                    apdu_number,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Segmentation fields of a segmented APDU
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct ApduSegmentation {
+    product_file_id: u16,
+    product_file_length: u16,
+    apdu_number: u16,
+}
+
+impl ApduSegmentation {
+    pub fn get_product_file_id(&self) -> u16 {
+        self.product_file_id
+    }
+
+    /// Number of APDUs in the product file
+    pub fn get_product_file_length(&self) -> u16 {
+        self.product_file_length
+    }
+
+    /// Position of this APDU in the product file (starting from 1)
+    pub fn get_apdu_number(&self) -> u16 {
+        self.apdu_number
+    }
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum FrameType {
+    FisbApdu,
+    Reserved1,
+    Reserved2,
+    Reserved3,
+    Reserved4,
+    Reserved5,
+    Reserved6,
+    Reserved7,
+    Reserved8,
+    Reserved9,
+    Reserved10,
+    Reserved11,
+    Reserved12,
+    Reserved13,
+    DevelopmentalUse,
+    ServiceStatus,
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use deku::DekuContainerWrite;
+
+    use super::{Apdu, FrameType, InformationFrame, InformationFrames};
+    use crate::error::GDL90Error;
+
+    /// APDU for a product issued at 12:34, with the given payload
+    pub(crate) fn build_apdu(product_id: u16, payload: &[u8]) -> Apdu {
+        let mut data = vec![
+            (product_id >> 6) as u8,
+            (product_id << 2) as u8,
+            0x32, // 12:34
+            0x20,
+        ];
+        data.extend_from_slice(payload);
+        Apdu::try_from(&data[..]).unwrap()
+    }
+
+    /// Pack (value, bit count) fields, most significant bit first
+    pub(crate) fn pack(fields: &[(u32, usize)]) -> Vec<u8> {
+        let bits = fields
+            .iter()
+            .flat_map(|&(value, count)| (0..count).rev().map(move |bit| (value >> bit) & 1))
+            .collect::<Vec<_>>();
+
+        bits.chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .chain(std::iter::repeat(&0))
+                    .take(8)
+                    .fold(0, |acc, &bit| (acc << 1) | bit as u8)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_information_frames() {
+        let mut data = vec![
+            0x03, 0x80, 0x00, 0xFC, 0x32, 0x20, 0x01, 0x02, 0x03, // APDU
+            0x02, 0x0F, 0xAA, 0xBB, 0xCC, 0xDD, // Service status
+        ];
+        data.resize(424, 0);
+
+        let frames = InformationFrames::new(&data)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].get_frame_type(), FrameType::FisbApdu);
+        assert_eq!(frames[1].get_frame_type(), FrameType::ServiceStatus);
+        assert_eq!(frames[1].get_data(), &[0xAA, 0xBB, 0xCC, 0xDD]);
+        assert!(frames[1].get_apdu().is_err());
+
+        let apdu = frames[0].get_apdu().unwrap();
+        let header = apdu.get_header();
+
+        assert!(!header.get_a_flag());
+        assert!(!header.get_g_flag());
+        assert!(!header.get_p_flag());
+        assert!(!header.get_s_flag());
+        assert_eq!(header.get_product_id(), 63);
+        assert_eq!(header.get_month(), None);
+        assert_eq!(header.get_day(), None);
+        assert_eq!(header.get_hours(), 12);
+        assert_eq!(header.get_minutes(), 34);
+        assert_eq!(header.get_seconds(), None);
+        assert_eq!(header.get_segmentation(), None);
+        assert_eq!(apdu.get_payload(), &[0x01, 0x02, 0x03]);
+
+        let mut frame = InformationFrame::default();
+        frame.set_data(frames[0].get_data().to_vec()).unwrap();
+        assert_eq!(&frame.to_bytes().unwrap()[..], &data[..9]);
+
+        assert!(frame.set_data(vec![0; 511]).is_ok());
+        assert!(matches!(
+            frame.set_data(vec![0; 512]),
+            Err(GDL90Error::InvalidParam(_))
+        ));
+        assert_eq!(frame.get_data().len(), 511);
+    }
+
+    #[test]
+    fn test_segmented_apdu_all_time_fields() {
+        let data = [0xC6, 0x77, 0xD4, 0x59, 0x17, 0x00, 0x2C, 0xB0, 0x04, 0xEE];
+
+        let apdu = Apdu::try_from(&data[..]).unwrap();
+        let header = apdu.get_header();
+
+        assert!(header.get_a_flag());
+        assert!(header.get_g_flag());
+        assert!(!header.get_p_flag());
+        assert!(header.get_s_flag());
+        assert_eq!(header.get_product_id(), 413);
+        assert_eq!(header.get_month(), Some(10));
+        assert_eq!(header.get_day(), Some(17));
+        assert_eq!(header.get_hours(), 12);
+        assert_eq!(header.get_minutes(), 34);
+        assert_eq!(header.get_seconds(), Some(56));
+
+        let segmentation = header.get_segmentation().unwrap();
+        assert_eq!(segmentation.get_product_file_id(), 5);
+        assert_eq!(segmentation.get_product_file_length(), 300);
+        assert_eq!(segmentation.get_apdu_number(), 2);
+        assert_eq!(apdu.get_payload(), &[0xEE]);
+    }
+
+    #[test]
+    fn test_truncated_frame() {
+        let data = [0x03, 0x80, 0x00, 0xFC];
+
+        let mut frames = InformationFrames::new(&data);
+
+        assert!(matches!(
+            frames.next(),
+            Some(Err(GDL90Error::Incomplete(_)))
+        ));
+        assert!(frames.next().is_none());
+    }
+}
@@ -0,0 +1,420 @@
+//! FIS-B NEXRAD global block representation (product IDs 63 and 64)
+//!
+//! Each block covers 4 arc-minutes of latitude by 48 arc-minutes of longitude (96
+//! arc-minutes north of 60 degrees) and is divided into 4 rows of 32 bins. Rows are
+//! ordered north to south and bins west to east.
+use crate::{error::GDL90Error, fisb::Apdu};
+use enum_ordinalize::Ordinalize;
+
+/// Regional NEXRAD product ID
+pub const REGIONAL_NEXRAD_ID: u16 = 63;
+
+/// CONUS NEXRAD product ID
+pub const CONUS_NEXRAD_ID: u16 = 64;
+
+/// Number of bin rows in a block
+pub const NEXRAD_BLOCK_ROWS: usize = 4;
+
+/// Number of bin columns in a block
+pub const NEXRAD_BLOCK_COLUMNS: usize = 32;
+
+const NEXRAD_BLOCK_BINS: usize = NEXRAD_BLOCK_ROWS * NEXRAD_BLOCK_COLUMNS;
+
+const BLOCKS_PER_RING: u32 = 450;
+const BLOCK_THRESHOLD: u32 = 405000; // First block north of 60 degrees
+const BLOCK_HEIGHT: f32 = 4. / 60.;
+const BLOCK_WIDTH: f32 = 48. / 60.;
+const WIDE_BLOCK_WIDTH: f32 = 96. / 60.;
+
+/// NEXRAD block of intensity bins
+#[derive(Debug, PartialEq, Clone)]
+pub struct NexradBlock {
+    product_id: u16,
+    block_number: u32,
+    southern_hemisphere: bool,
+    scale_factor: u8,
+    bins: [u8; NEXRAD_BLOCK_BINS],
+}
+
+impl NexradBlock {
+    /// FIS-B product ID (63 regional, 64 CONUS)
+    pub fn get_product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    pub fn get_block_number(&self) -> u32 {
+        self.block_number
+    }
+
+    pub fn get_southern_hemisphere(&self) -> bool {
+        self.southern_hemisphere
+    }
+
+    pub fn get_scale_factor(&self) -> ScaleFactor {
+        ScaleFactor::VARIANTS[self.scale_factor as usize]
+    }
+
+    /// Intensity levels (0 - 7) of every bin, row by row
+    pub fn get_bins(&self) -> &[u8; NEXRAD_BLOCK_BINS] {
+        &self.bins
+    }
+
+    /// Intensity level (0 - 7) of a single bin
+    pub fn get_intensity(&self, row: usize, column: usize) -> Option<u8> {
+        if row < NEXRAD_BLOCK_ROWS && column < NEXRAD_BLOCK_COLUMNS {
+            Some(self.bins[row * NEXRAD_BLOCK_COLUMNS + column])
+        } else {
+            None
+        }
+    }
+
+    /// Geographic bounds of the block (in degrees)
+    pub fn get_bounds(&self) -> Bounds {
+        // Only even block numbers are used north of 60 degrees
+        let block_number = if self.block_number >= BLOCK_THRESHOLD {
+            self.block_number & !1
+        } else {
+            self.block_number
+        };
+
+        let scale = self.get_scale_factor().get_scale();
+        let height = BLOCK_HEIGHT * scale;
+        let width = if block_number >= BLOCK_THRESHOLD {
+            WIDE_BLOCK_WIDTH
+        } else {
+            BLOCK_WIDTH
+        } * scale;
+
+        let ring_latitude = (block_number / BLOCKS_PER_RING) as f32 * BLOCK_HEIGHT;
+        let (north, south) = if self.southern_hemisphere {
+            (-ring_latitude, -ring_latitude - height)
+        } else {
+            (ring_latitude + height, ring_latitude)
+        };
+
+        let mut west = (block_number % BLOCKS_PER_RING) as f32 * BLOCK_WIDTH;
+        if west >= 180. {
+            west -= 360.;
+        }
+
+        Bounds {
+            north,
+            south,
+            east: west + width,
+            west,
+        }
+    }
+
+    /// Geographic bounds of a single bin (in degrees)
+    pub fn get_bin_bounds(&self, row: usize, column: usize) -> Option<Bounds> {
+        if row >= NEXRAD_BLOCK_ROWS || column >= NEXRAD_BLOCK_COLUMNS {
+            return None;
+        }
+
+        let bounds = self.get_bounds();
+        let height = (bounds.north - bounds.south) / NEXRAD_BLOCK_ROWS as f32;
+        let width = (bounds.east - bounds.west) / NEXRAD_BLOCK_COLUMNS as f32;
+
+        let north = bounds.north - row as f32 * height;
+        let west = bounds.west + column as f32 * width;
+
+        Some(Bounds {
+            north,
+            south: north - height,
+            east: west + width,
+            west,
+        })
+    }
+}
+
+/// Geographic bounding box (in degrees)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Bounds {
+    pub north: f32,
+    pub south: f32,
+    pub east: f32,
+    pub west: f32,
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum ScaleFactor {
+    High,
+    Medium,
+    Low,
+    Reserved,
+}
+
+impl ScaleFactor {
+    /// Block size multiplier
+    pub fn get_scale(&self) -> f32 {
+        match self {
+            ScaleFactor::Medium => 5.,
+            ScaleFactor::Low => 9.,
+            ScaleFactor::High | ScaleFactor::Reserved => 1.,
+        }
+    }
+}
+
+/// Decode the NEXRAD blocks in a regional or CONUS NEXRAD [Apdu]
+///
+/// A run-length encoded APDU contains a single block. An empty block APDU contains
+/// one or more blocks in which every bin has an intensity of zero.
+pub fn decode_nexrad(apdu: &Apdu) -> Result<Vec<NexradBlock>, GDL90Error> {
+    let product_id = apdu.get_header().get_product_id();
+    if product_id != REGIONAL_NEXRAD_ID && product_id != CONUS_NEXRAD_ID {
+        return Err(GDL90Error::Parse(
+            format!("Product {product_id} is not a NEXRAD product").into(),
+        ));
+    }
+
+    let (header, data) = match apdu.get_payload() {
+        [b0, b1, b2, data @ ..] => ([*b0, *b1, *b2], data),
+        _ => return Err(GDL90Error::Incomplete(24)),
+    };
+
+    let run_length_encoded = header[0] & 0x80 != 0;
+    let southern_hemisphere = header[0] & 0x40 != 0;
+    let scale_factor = (header[0] & 0x30) >> 4;
+    let block_number = u32::from_be_bytes([0, header[0] & 0x0F, header[1], header[2]]);
+
+    let block = |block_number, bins| NexradBlock {
+        product_id,
+        block_number,
+        southern_hemisphere,
+        scale_factor,
+        bins,
+    };
+
+    if run_length_encoded {
+        Ok(vec![block(block_number, decode_run_length(data)?)])
+    } else {
+        Ok(decode_empty_blocks(block_number, data)?
+            .into_iter()
+            .map(|block_number| block(block_number, [0; NEXRAD_BLOCK_BINS]))
+            .collect())
+    }
+}
+
+fn decode_run_length(data: &[u8]) -> Result<[u8; NEXRAD_BLOCK_BINS], GDL90Error> {
+    let mut bins = [0; NEXRAD_BLOCK_BINS];
+    let mut idx = 0;
+
+    for byte in data {
+        let intensity = byte & 0x07;
+        let run_length = (byte >> 3) as usize + 1;
+
+        if idx + run_length > NEXRAD_BLOCK_BINS {
+            return Err(GDL90Error::Parse(
+                "NEXRAD run length overflows block".into(),
+            ));
+        }
+
+        bins[idx..idx + run_length].fill(intensity);
+        idx += run_length;
+    }
+
+    if idx == NEXRAD_BLOCK_BINS {
+        Ok(bins)
+    } else {
+        Err(GDL90Error::Parse(
+            format!("NEXRAD run length covers {idx} of {NEXRAD_BLOCK_BINS} bins").into(),
+        ))
+    }
+}
+
+fn decode_empty_blocks(block_number: u32, data: &[u8]) -> Result<Vec<u32>, GDL90Error> {
+    let Some(first) = data.first() else {
+        return Err(GDL90Error::Incomplete(8));
+    };
+
+    let bitmap_length = (first & 0x0F) as usize;
+    if data.len() < bitmap_length {
+        return Err(GDL90Error::Incomplete(bitmap_length * 8));
+    }
+
+    // Block numbers wrap around within the ring of the first block
+    let ring_start = block_number - block_number % BLOCKS_PER_RING;
+    let ring_offset = block_number % BLOCKS_PER_RING;
+    let wrap = |offset: u32| ring_start + (ring_offset + offset) % BLOCKS_PER_RING;
+
+    let mut blocks = vec![block_number];
+
+    // The high nibble of the first byte flags the next 4 blocks
+    for bit in 4..8 {
+        if first & (1 << bit) != 0 {
+            blocks.push(wrap(bit - 3));
+        }
+    }
+
+    // Subsequent bytes flag 8 blocks each
+    for (idx, byte) in data.iter().enumerate().take(bitmap_length).skip(1) {
+        for bit in 0..8 {
+            if byte & (1 << bit) != 0 {
+                blocks.push(wrap(idx as u32 * 8 + bit - 3));
+            }
+        }
+    }
+
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_nexrad, ScaleFactor, NEXRAD_BLOCK_COLUMNS};
+    use crate::fisb::test::build_apdu;
+
+    #[test]
+    fn test_run_length_block() {
+        let block_number: u32 = 771 * 450 + 449;
+        let mut payload = vec![
+            0x80 | (block_number >> 16) as u8,
+            (block_number >> 8) as u8,
+            block_number as u8,
+        ];
+        payload.extend([
+            0xF8, // 32 bins of level 0
+            0x7B, // 16 bins of level 3
+            0x7F, // 16 bins of level 7
+            0xF9, // 32 bins of level 1
+            0xF8, // 32 bins of level 0
+        ]);
+
+        let blocks = decode_nexrad(&build_apdu(63, &payload)).unwrap();
+        assert_eq!(blocks.len(), 1);
+
+        let block = &blocks[0];
+        assert_eq!(block.get_product_id(), 63);
+        assert_eq!(block.get_block_number(), block_number);
+        assert_eq!(block.get_scale_factor(), ScaleFactor::High);
+        assert!(!block.get_southern_hemisphere());
+        assert_eq!(block.get_intensity(0, 31), Some(0));
+        assert_eq!(block.get_intensity(1, 15), Some(3));
+        assert_eq!(block.get_intensity(1, 16), Some(7));
+        assert_eq!(block.get_intensity(2, 0), Some(1));
+        assert_eq!(block.get_intensity(3, 31), Some(0));
+        assert_eq!(block.get_intensity(4, 0), None);
+
+        let bounds = block.get_bounds();
+        assert!((bounds.south - 51.4).abs() < 0.0001);
+        assert!((bounds.north - 51.466_667).abs() < 0.0001);
+        assert!((bounds.west + 0.8).abs() < 0.0001);
+        assert!(bounds.east.abs() < 0.0001);
+
+        let bin = block.get_bin_bounds(3, NEXRAD_BLOCK_COLUMNS - 1).unwrap();
+        assert!((bin.south - bounds.south).abs() < 0.0001);
+        assert!((bin.east - bounds.east).abs() < 0.0001);
+        assert!((bin.west + 0.025).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_run_length_block_incomplete() {
+        let payload = [0x80, 0x00, 0x01, 0xF9];
+        assert!(decode_nexrad(&build_apdu(64, &payload)).is_err());
+    }
+
+    #[test]
+    fn test_empty_blocks() {
+        let block_number: u32 = 1000;
+        let payload = [
+            (block_number >> 16) as u8 | 0x10, // Medium scale
+            (block_number >> 8) as u8,
+            block_number as u8,
+            0x52, // Blocks +1 and +3, bitmap length 2
+            0x81, // Blocks +5 and +12
+        ];
+
+        let blocks = decode_nexrad(&build_apdu(64, &payload)).unwrap();
+        let numbers = blocks
+            .iter()
+            .map(|block| block.get_block_number())
+            .collect::<Vec<_>>();
+
+        assert_eq!(numbers, vec![1000, 1001, 1003, 1005, 1012]);
+        assert!(blocks.iter().all(|block| block.get_bins() == &[0; 128]));
+        assert_eq!(blocks[0].get_scale_factor(), ScaleFactor::Medium);
+    }
+
+    #[test]
+    fn test_empty_blocks_row_wrap() {
+        let block_number: u32 = 771 * 450 + 447;
+        let payload = [
+            (block_number >> 16) as u8,
+            (block_number >> 8) as u8,
+            block_number as u8,
+            0x72, // Blocks +1, +2 and +3, bitmap length 2
+            0x01, // Block +5
+        ];
+
+        let blocks = decode_nexrad(&build_apdu(64, &payload)).unwrap();
+        let numbers = blocks
+            .iter()
+            .map(|block| block.get_block_number())
+            .collect::<Vec<_>>();
+
+        // The run crosses the end of the row and continues from its start
+        let row_start = 771 * 450;
+        assert_eq!(
+            numbers,
+            vec![
+                block_number,
+                row_start + 448,
+                row_start + 449,
+                row_start,
+                row_start + 2
+            ]
+        );
+
+        // North of 60 degrees rings keep 450 block numbers, only even ones being used
+        let block_number: u32 = 405_000 + 224;
+        let payload = [
+            (block_number >> 16) as u8,
+            (block_number >> 8) as u8,
+            block_number as u8,
+            0x11, // Block +1, bitmap length 1
+        ];
+
+        let blocks = decode_nexrad(&build_apdu(64, &payload)).unwrap();
+        assert_eq!(blocks[1].get_block_number(), 405_225);
+
+        // Crossing 180 degrees north of 60 degrees wraps to the start of the ring
+        let block_number: u32 = 405_000 + 448;
+        let payload = [
+            (block_number >> 16) as u8,
+            (block_number >> 8) as u8,
+            block_number as u8,
+            0x31, // Blocks +1 and +2, bitmap length 1
+        ];
+
+        let blocks = decode_nexrad(&build_apdu(64, &payload)).unwrap();
+        let numbers = blocks
+            .iter()
+            .map(|block| block.get_block_number())
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![block_number, 405_449, 405_000]);
+        assert_eq!(blocks[2].get_bounds().west, 0.);
+    }
+
+    #[test]
+    fn test_wide_southern_block() {
+        let block_number: u32 = 405_001;
+        let payload = [
+            0x40 | (block_number >> 16) as u8,
+            (block_number >> 8) as u8,
+            block_number as u8,
+            0x00,
+        ];
+
+        let blocks = decode_nexrad(&build_apdu(63, &payload)).unwrap();
+        let bounds = blocks[0].get_bounds();
+
+        assert!((bounds.north + 60.).abs() < 0.0001);
+        assert!((bounds.south + 60.066_666).abs() < 0.0001);
+        assert!((bounds.east - 1.6).abs() < 0.0001);
+        assert_eq!(bounds.west, 0.);
+    }
+
+    #[test]
+    fn test_not_nexrad() {
+        assert!(decode_nexrad(&build_apdu(413, &[0x80, 0x00, 0x00])).is_err());
+    }
+}
@@ -0,0 +1,763 @@
+//! FIS-B text and graphical overlay (TWGO) products
+//!
+//! Covers NOTAM-TFR (8), AIRMET (11), SIGMET (12), SUA (13), G-AIRMET (14) and CWA (15)
+//! products. Each product contains either text records or graphical records. A
+//! graphical record references its text record through the report number.
+use crate::{
+    error::GDL90Error,
+    fisb::{text::decode_dlac, Apdu},
+};
+use deku::prelude::*;
+use enum_ordinalize::Ordinalize;
+
+/// Product IDs using the text and graphical overlay format
+pub const OVERLAY_PRODUCT_IDS: [u16; 6] = [8, 11, 12, 13, 14, 15];
+
+const TEXT_RECORD_FORMAT: u8 = 2;
+const GRAPHICAL_RECORD_FORMAT: u8 = 8;
+
+const TEXT_RECORD_HEADER_LENGTH: usize = 5;
+const EXTENDED_VERTEX_LENGTH: usize = 6;
+const CIRCULAR_PRISM_LENGTH: usize = 14;
+
+/// Decoded text or graphical overlay product
+#[derive(Debug, PartialEq, Clone)]
+pub struct OverlayProduct {
+    product_id: u16,
+    product_version: u8,
+    location_identifier: String,
+    records: Vec<OverlayRecord>,
+}
+
+impl OverlayProduct {
+    pub fn get_product_id(&self) -> u16 {
+        self.product_id
+    }
+
+    pub fn get_product_version(&self) -> u8 {
+        self.product_version
+    }
+
+    /// Location identifier, e.g. the issuing center or state
+    pub fn get_location_identifier(&self) -> &str {
+        &self.location_identifier
+    }
+
+    pub fn get_records(&self) -> &[OverlayRecord] {
+        &self.records
+    }
+}
+
+/// Overlay product record
+#[derive(Debug, PartialEq, Clone)]
+pub enum OverlayRecord {
+    Text(TextRecord),
+    Graphical(GraphicalRecord),
+}
+
+/// Text record
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextRecord {
+    report_number: u16,
+    report_year: u8,
+    report_active: bool,
+    text: String,
+}
+
+impl TextRecord {
+    /// Report number, shared with the graphical records for the same report
+    pub fn get_report_number(&self) -> u16 {
+        self.report_number
+    }
+
+    /// Report year (two digits)
+    pub fn get_report_year(&self) -> u8 {
+        self.report_year
+    }
+
+    /// Report is active, rather than cancelled
+    pub fn get_report_active(&self) -> bool {
+        self.report_active
+    }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Graphical record
+#[derive(Debug, PartialEq, Clone)]
+pub struct GraphicalRecord {
+    report_number: u16,
+    report_year: u8,
+    report_active: bool,
+    overlay_record_identifier: u8,
+    object_label: ObjectLabel,
+    object_element: u8,
+    object_type: u8,
+    object_status: u8,
+    start_time: Option<OverlayTime>,
+    end_time: Option<OverlayTime>,
+    geometry: Geometry,
+}
+
+impl GraphicalRecord {
+    /// Report number of the [TextRecord] for this report
+    pub fn get_report_number(&self) -> u16 {
+        self.report_number
+    }
+
+    /// Report year (two digits)
+    pub fn get_report_year(&self) -> u8 {
+        self.report_year
+    }
+
+    /// Report is active, rather than cancelled
+    pub fn get_report_active(&self) -> bool {
+        self.report_active
+    }
+
+    /// Identifies the overlay within a report (starting from 1)
+    pub fn get_overlay_record_identifier(&self) -> u8 {
+        self.overlay_record_identifier
+    }
+
+    pub fn get_object_label(&self) -> &ObjectLabel {
+        &self.object_label
+    }
+
+    pub fn get_object_element(&self) -> u8 {
+        self.object_element
+    }
+
+    pub fn get_object_type(&self) -> u8 {
+        self.object_type
+    }
+
+    pub fn get_object_status(&self) -> u8 {
+        self.object_status
+    }
+
+    /// Start of the validity period, if any
+    pub fn get_start_time(&self) -> Option<OverlayTime> {
+        self.start_time
+    }
+
+    /// End of the validity period, if any
+    pub fn get_end_time(&self) -> Option<OverlayTime> {
+        self.end_time
+    }
+
+    pub fn get_geometry(&self) -> &Geometry {
+        &self.geometry
+    }
+}
+
+/// Object label of a graphical record
+#[derive(Debug, PartialEq, Clone)]
+pub enum ObjectLabel {
+    Numeric(u16),
+    Text(String),
+}
+
+/// Validity time (UTC), fields are present depending on the date/time format
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OverlayTime {
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl OverlayTime {
+    fn parse(format: u8, data: [u8; 4]) -> Option<Self> {
+        match format {
+            1 => Some(OverlayTime {
+                month: Some(data[0]),
+                day: Some(data[1]),
+                hour: data[2],
+                minute: data[3],
+            }),
+            2 => Some(OverlayTime {
+                month: None,
+                day: Some(data[0]),
+                hour: data[1],
+                minute: data[2],
+            }),
+            3 => Some(OverlayTime {
+                month: None,
+                day: None,
+                hour: data[0],
+                minute: data[1],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Overlay geometry
+#[derive(Debug, PartialEq, Clone)]
+pub enum Geometry {
+    None,
+    Polygon(AltitudeReference, Vec<Vertex>),
+    Polyline(AltitudeReference, Vec<Vertex>),
+    Point(AltitudeReference, Vertex),
+    CircularPrism(AltitudeReference, CircularPrism),
+    /// Geometry overlay option that is not decoded
+    Unsupported(u8),
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum AltitudeReference {
+    MSL,
+    AGL,
+}
+
+/// Geometry vertex
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Vertex {
+    /// Degrees
+    pub latitude: f32,
+    /// Degrees
+    pub longitude: f32,
+    /// Feet
+    pub altitude: i32,
+}
+
+/// Circular (or elliptical) prism
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CircularPrism {
+    /// Center of the bottom face, altitude in feet
+    pub bottom: Vertex,
+    /// Center of the top face, altitude in feet
+    pub top: Vertex,
+    /// Radius along the longitude axis (in nautical miles)
+    pub radius_longitude: f32,
+    /// Radius along the latitude axis (in nautical miles)
+    pub radius_latitude: f32,
+    /// Orientation of the latitude axis (in degrees)
+    pub orientation: u8,
+}
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+struct ProductHeader {
+    #[deku(bits = "4")]
+    record_format: u8,
+
+    #[deku(bits = "4")]
+    product_version: u8,
+
+    #[deku(bits = "4")]
+    record_count: u8,
+
+    #[deku(bits = "4")]
+    reserved: u8,
+
+    location_identifier: [u8; 3],
+
+    record_reference_point: u8,
+}
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+struct TextRecordHeader {
+    record_length: u16,
+
+    #[deku(bits = "14")]
+    report_number: u16,
+
+    #[deku(bits = "7")]
+    report_year: u8,
+
+    #[deku(bits = "1")]
+    report_active: bool,
+
+    #[deku(bits = "2")]
+    reserved: u8,
+}
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+struct GraphicalRecordHeader {
+    #[deku(bits = "10")]
+    record_length: u16,
+
+    #[deku(bits = "14")]
+    report_number: u16,
+
+    #[deku(bits = "7")]
+    report_year: u8,
+
+    #[deku(bits = "1")]
+    report_active: bool,
+
+    reserved1: u8,
+
+    #[deku(bits = "3")]
+    reserved2: u8,
+
+    #[deku(bits = "4")]
+    overlay_record_identifier: u8,
+
+    #[deku(bits = "1")]
+    object_label_flag: bool,
+
+    #[deku(cond = "!*object_label_flag")]
+    numeric_label: Option<u16>,
+
+    #[deku(cond = "*object_label_flag")]
+    text_label: Option<[u8; 9]>,
+
+    #[deku(bits = "1")]
+    element_flag: bool,
+
+    #[deku(bits = "1")]
+    qualifier_flag: bool,
+
+    #[deku(bits = "1")]
+    parameter_flag: bool,
+
+    #[deku(bits = "5")]
+    object_element: u8,
+
+    #[deku(bits = "4")]
+    object_type: u8,
+
+    #[deku(bits = "4")]
+    object_status: u8,
+
+    #[deku(bits = "24", cond = "*qualifier_flag")]
+    object_qualifier: Option<u32>,
+
+    #[deku(cond = "*parameter_flag")]
+    object_parameters: Option<u16>,
+
+    #[deku(bits = "2")]
+    record_applicability_options: u8,
+
+    #[deku(bits = "2")]
+    date_time_format: u8,
+
+    #[deku(bits = "4")]
+    geometry_overlay_options: u8,
+
+    #[deku(bits = "2")]
+    overlay_operator: u8,
+
+    #[deku(bits = "6")]
+    overlay_vertices_count: u8,
+
+    #[deku(cond = "*record_applicability_options & 0b01 != 0")]
+    start_time: Option<[u8; 4]>,
+
+    #[deku(cond = "*record_applicability_options & 0b10 != 0")]
+    end_time: Option<[u8; 4]>,
+}
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+struct ExtendedVertex {
+    #[deku(bits = "19")]
+    longitude: u32,
+
+    #[deku(bits = "19")]
+    latitude: u32,
+
+    #[deku(bits = "10")]
+    altitude: u16,
+}
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+struct ExtendedCircularPrism {
+    #[deku(bits = "18")]
+    bottom_longitude: u32,
+
+    #[deku(bits = "18")]
+    bottom_latitude: u32,
+
+    #[deku(bits = "18")]
+    top_longitude: u32,
+
+    #[deku(bits = "18")]
+    top_latitude: u32,
+
+    #[deku(bits = "7")]
+    bottom_altitude: u16,
+
+    #[deku(bits = "7")]
+    top_altitude: u16,
+
+    #[deku(bits = "9")]
+    radius_longitude: u16,
+
+    #[deku(bits = "9")]
+    radius_latitude: u16,
+
+    orientation: u8,
+}
+
+/// Decode a text or graphical overlay product [Apdu]
+pub fn decode_overlay(apdu: &Apdu) -> Result<OverlayProduct, GDL90Error> {
+    let product_id = apdu.get_header().get_product_id();
+    if !OVERLAY_PRODUCT_IDS.contains(&product_id) {
+        return Err(GDL90Error::Parse(
+            format!("Product {product_id} is not an overlay product").into(),
+        ));
+    }
+
+    let ((mut data, _), header) = ProductHeader::from_bytes((apdu.get_payload(), 0))?;
+
+    let mut records = vec![];
+    for _ in 0..header.record_count {
+        if data.is_empty() {
+            break;
+        }
+
+        let (record, length) = match header.record_format {
+            TEXT_RECORD_FORMAT => decode_text_record(data)?,
+            GRAPHICAL_RECORD_FORMAT => decode_graphical_record(data)?,
+            format => {
+                return Err(GDL90Error::Parse(
+                    format!("Unsupported overlay record format {format}").into(),
+                ))
+            }
+        };
+
+        records.push(record);
+        data = data
+            .get(length..)
+            .ok_or(GDL90Error::Incomplete(length * 8))?;
+    }
+
+    Ok(OverlayProduct {
+        product_id,
+        product_version: header.product_version,
+        location_identifier: decode_dlac(&header.location_identifier)
+            .trim_end()
+            .to_owned(),
+        records,
+    })
+}
+
+fn decode_text_record(data: &[u8]) -> Result<(OverlayRecord, usize), GDL90Error> {
+    let (_, header) = TextRecordHeader::from_bytes((data, 0))?;
+
+    let length = header.record_length as usize;
+    let text = data
+        .get(TEXT_RECORD_HEADER_LENGTH..length)
+        .ok_or(GDL90Error::Incomplete(length * 8))?;
+
+    let record = TextRecord {
+        report_number: header.report_number,
+        report_year: header.report_year,
+        report_active: header.report_active,
+        text: decode_dlac(text),
+    };
+
+    Ok((OverlayRecord::Text(record), length))
+}
+
+fn decode_graphical_record(data: &[u8]) -> Result<(OverlayRecord, usize), GDL90Error> {
+    let ((rest, _), header) = GraphicalRecordHeader::from_bytes((data, 0))?;
+
+    let object_label = match (header.numeric_label, header.text_label) {
+        (Some(label), _) => ObjectLabel::Numeric(label),
+        (_, Some(label)) => ObjectLabel::Text(decode_dlac(&label).trim_end().to_owned()),
+        _ => ObjectLabel::Numeric(0),
+    };
+
+    let vertices_count = header.overlay_vertices_count as usize + 1;
+    let geometry = match header.geometry_overlay_options {
+        0 => Geometry::None,
+        3 => Geometry::Polygon(
+            AltitudeReference::MSL,
+            decode_vertices(rest, vertices_count)?,
+        ),
+        4 => Geometry::Polyline(
+            AltitudeReference::MSL,
+            decode_vertices(rest, vertices_count)?,
+        ),
+        5 => Geometry::Polygon(
+            AltitudeReference::AGL,
+            decode_vertices(rest, vertices_count)?,
+        ),
+        6 => Geometry::Polyline(
+            AltitudeReference::AGL,
+            decode_vertices(rest, vertices_count)?,
+        ),
+        7 => Geometry::CircularPrism(AltitudeReference::MSL, decode_circular_prism(rest)?),
+        8 => Geometry::CircularPrism(AltitudeReference::AGL, decode_circular_prism(rest)?),
+        9 => Geometry::Point(AltitudeReference::AGL, decode_vertex(rest)?),
+        10 => Geometry::Point(AltitudeReference::MSL, decode_vertex(rest)?),
+        option => Geometry::Unsupported(option),
+    };
+
+    let record = GraphicalRecord {
+        report_number: header.report_number,
+        report_year: header.report_year,
+        report_active: header.report_active,
+        overlay_record_identifier: header.overlay_record_identifier + 1,
+        object_label,
+        object_element: header.object_element,
+        object_type: header.object_type,
+        object_status: header.object_status,
+        start_time: header
+            .start_time
+            .and_then(|time| OverlayTime::parse(header.date_time_format, time)),
+        end_time: header
+            .end_time
+            .and_then(|time| OverlayTime::parse(header.date_time_format, time)),
+        geometry,
+    };
+
+    Ok((
+        OverlayRecord::Graphical(record),
+        header.record_length as usize,
+    ))
+}
+
+fn decode_vertices(data: &[u8], count: usize) -> Result<Vec<Vertex>, GDL90Error> {
+    let length = count * EXTENDED_VERTEX_LENGTH;
+    let data = data
+        .get(..length)
+        .ok_or(GDL90Error::Incomplete(length * 8))?;
+
+    data.chunks(EXTENDED_VERTEX_LENGTH)
+        .map(decode_vertex)
+        .collect()
+}
+
+fn decode_vertex(data: &[u8]) -> Result<Vertex, GDL90Error> {
+    let (_, vertex) = ExtendedVertex::from_bytes((data, 0))?;
+
+    Ok(Vertex {
+        latitude: angle_to_f32(vertex.latitude, 19),
+        longitude: angle_to_f32(vertex.longitude, 19),
+        altitude: vertex.altitude as i32 * 100,
+    })
+}
+
+fn decode_circular_prism(data: &[u8]) -> Result<CircularPrism, GDL90Error> {
+    let data = data
+        .get(..CIRCULAR_PRISM_LENGTH)
+        .ok_or(GDL90Error::Incomplete(CIRCULAR_PRISM_LENGTH * 8))?;
+    let (_, prism) = ExtendedCircularPrism::from_bytes((data, 0))?;
+
+    Ok(CircularPrism {
+        bottom: Vertex {
+            latitude: angle_to_f32(prism.bottom_latitude, 18),
+            longitude: angle_to_f32(prism.bottom_longitude, 18),
+            altitude: prism.bottom_altitude as i32 * 500,
+        },
+        top: Vertex {
+            latitude: angle_to_f32(prism.top_latitude, 18),
+            longitude: angle_to_f32(prism.top_longitude, 18),
+            altitude: prism.top_altitude as i32 * 500,
+        },
+        radius_longitude: prism.radius_longitude as f32 * 0.2,
+        radius_latitude: prism.radius_latitude as f32 * 0.2,
+        orientation: prism.orientation,
+    })
+}
+
+/// Convert a two's complement angle, where the full range covers 360 degrees
+fn angle_to_f32(value: u32, bits: u32) -> f32 {
+    let angle = value as f32 * 360. / (1 << bits) as f32;
+    if angle > 180. {
+        angle - 360.
+    } else {
+        angle
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_overlay, AltitudeReference, Geometry, ObjectLabel, OverlayRecord};
+    use crate::fisb::{
+        test::{build_apdu, pack},
+        text::encode_dlac,
+    };
+
+    fn product_header(record_format: u32, record_count: u32) -> Vec<u8> {
+        let mut header = pack(&[(record_format, 4), (1, 4), (record_count, 4), (0, 4)]);
+        header.extend(&encode_dlac("ZNY").unwrap()[..3]);
+        header.push(0);
+        header
+    }
+
+    #[test]
+    fn test_text_record() {
+        let text = encode_dlac("FDC 4/1234 ZNY NY..TFR").unwrap();
+
+        let mut payload = product_header(2, 1);
+        payload.extend(pack(&[
+            (text.len() as u32 + 5, 16),
+            (1234, 14),
+            (24, 7),
+            (1, 1),
+            (0, 2),
+        ]));
+        payload.extend(&text);
+
+        let product = decode_overlay(&build_apdu(8, &payload)).unwrap();
+        assert_eq!(product.get_product_id(), 8);
+        assert_eq!(product.get_location_identifier(), "ZNY");
+
+        let OverlayRecord::Text(record) = &product.get_records()[0] else {
+            panic!("Unexpected record type");
+        };
+        assert_eq!(record.get_report_number(), 1234);
+        assert_eq!(record.get_report_year(), 24);
+        assert!(record.get_report_active());
+        assert_eq!(record.get_text(), "FDC 4/1234 ZNY NY..TFR");
+    }
+
+    #[test]
+    fn test_graphical_polygon() {
+        let vertex = |lat: f32, lon: f32, alt: u32| {
+            let scale = (1 << 19) as f32 / 360.;
+            let lat = ((lat * scale) as i32 as u32) & 0x7FFFF;
+            let lon = ((lon * scale) as i32 as u32) & 0x7FFFF;
+            pack(&[(lon, 19), (lat, 19), (alt, 10)])
+        };
+
+        let mut record = pack(&[
+            (0, 10), // Record length, updated below
+            (1234, 14),
+            (24, 7),
+            (1, 1),
+            (0, 8),
+            (0, 3),
+            (0, 4), // Overlay record identifier 1
+            (0, 1), // Numeric label
+            (42, 16),
+            (0, 3),
+            (14, 5), // Object element
+            (1, 4),
+            (2, 4),
+            (3, 2), // Start and end time
+            (2, 2), // Day, hours, minutes
+            (3, 4), // 3D polygon MSL
+            (0, 2),
+            (2, 6), // 3 vertices
+        ]);
+        record.extend([17, 12, 0, 0, 17, 18, 30, 0]);
+        record.extend(vertex(40.5, -73.5, 180));
+        record.extend(vertex(40.5, -74.0, 180));
+        record.extend(vertex(-41.0, 74.0, 20));
+
+        let length = record.len();
+        record[0] = (length >> 2) as u8;
+        record[1] |= (length << 6) as u8;
+
+        let mut payload = product_header(8, 1);
+        payload.extend(&record);
+
+        let product = decode_overlay(&build_apdu(11, &payload)).unwrap();
+        let OverlayRecord::Graphical(record) = &product.get_records()[0] else {
+            panic!("Unexpected record type");
+        };
+
+        assert_eq!(record.get_report_number(), 1234);
+        assert_eq!(record.get_overlay_record_identifier(), 1);
+        assert_eq!(record.get_object_label(), &ObjectLabel::Numeric(42));
+        assert_eq!(record.get_object_element(), 14);
+
+        let start = record.get_start_time().unwrap();
+        assert_eq!((start.day, start.hour, start.minute), (Some(17), 12, 0));
+        let end = record.get_end_time().unwrap();
+        assert_eq!((end.month, end.day, end.hour), (None, Some(17), 18));
+
+        let Geometry::Polygon(AltitudeReference::MSL, vertices) = record.get_geometry() else {
+            panic!("Unexpected geometry");
+        };
+        assert_eq!(vertices.len(), 3);
+        assert!((vertices[0].latitude - 40.5).abs() < 0.001);
+        assert!((vertices[0].longitude + 73.5).abs() < 0.001);
+        assert_eq!(vertices[0].altitude, 18000);
+        assert!((vertices[2].latitude + 41.0).abs() < 0.001);
+        assert!((vertices[2].longitude - 74.0).abs() < 0.001);
+        assert_eq!(vertices[2].altitude, 2000);
+    }
+
+    #[test]
+    fn test_graphical_circular_prism() {
+        let scale = (1 << 18) as f32 / 360.;
+        let lat = (38.9 * scale) as u32;
+        let lon = ((-77.0 * scale) as i32 as u32) & 0x3FFFF;
+
+        let mut record = pack(&[
+            (0, 10),
+            (99, 14),
+            (24, 7),
+            (1, 1),
+            (0, 8),
+            (0, 3),
+            (0, 4),
+            (1, 1), // Text label
+        ]);
+        record.extend(encode_dlac("P56").unwrap());
+        record.resize(record.len() + 9 - 3, 0);
+        record.extend(pack(&[
+            (0, 3),
+            (0, 5),
+            (0, 4),
+            (0, 4),
+            (0, 2), // No times
+            (0, 2),
+            (7, 4), // Circular prism MSL
+            (0, 2),
+            (0, 6),
+            (lon, 18),
+            (lat, 18),
+            (lon, 18),
+            (lat, 18),
+            (0, 7),
+            (36, 7),
+            (15, 9),
+            (15, 9),
+            (45, 8),
+        ]));
+
+        let length = record.len();
+        record[0] = (length >> 2) as u8;
+        record[1] |= (length << 6) as u8;
+
+        let mut payload = product_header(8, 1);
+        payload.extend(&record);
+
+        let product = decode_overlay(&build_apdu(13, &payload)).unwrap();
+        let OverlayRecord::Graphical(record) = &product.get_records()[0] else {
+            panic!("Unexpected record type");
+        };
+
+        assert_eq!(
+            record.get_object_label(),
+            &ObjectLabel::Text("P56".to_owned())
+        );
+        assert_eq!(record.get_start_time(), None);
+
+        let Geometry::CircularPrism(AltitudeReference::MSL, prism) = record.get_geometry() else {
+            panic!("Unexpected geometry");
+        };
+        assert!((prism.bottom.latitude - 38.9).abs() < 0.01);
+        assert!((prism.bottom.longitude + 77.0).abs() < 0.01);
+        assert_eq!(prism.bottom.altitude, 0);
+        assert_eq!(prism.top.altitude, 18000);
+        assert!((prism.radius_latitude - 3.0).abs() < 0.001);
+        assert_eq!(prism.orientation, 45);
+    }
+
+    #[test]
+    fn test_not_overlay() {
+        assert!(decode_overlay(&build_apdu(63, &[0x00; 8])).is_err());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let payload = product_header(8, 1);
+        assert!(decode_overlay(&build_apdu(11, &payload[..4])).is_err());
+    }
+}
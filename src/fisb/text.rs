@@ -0,0 +1,278 @@
+//! FIS-B text products (product ID 413) and DLAC character decoding
+//!
+//! Text products are encoded using the 6-bit Data Link Application Coding (DLAC)
+//! character set. Each product contains one or more records separated by record
+//! separator characters, with each record starting with its type, station identifier
+//! and issue time, e.g. `METAR KIND 261654Z 14008KT 10SM ...`.
+use crate::{error::GDL90Error, fisb::Apdu};
+
+/// Generic textual data product ID
+pub const TEXT_PRODUCT_ID: u16 = 413;
+
+const DLAC_ALPHABET: &[u8; 64] =
+    b"\x03ABCDEFGHIJKLMNOPQRSTUVWXYZ\x1A\t\x1E\n| !\"#$%&'()*+,-./0123456789:;<=>?";
+
+const DLAC_ETX: u8 = 0;
+const DLAC_TAB: u8 = 28;
+const RECORD_SEPARATOR: char = '\x1E';
+
+/// Decode DLAC encoded bytes into a string
+///
+/// Decoding stops at the end of text character. A tab character is followed by a
+/// count of spaces, which is expanded.
+pub fn decode_dlac(data: &[u8]) -> String {
+    let mut result = String::new();
+    let mut is_tab = false;
+
+    for ch in dlac_characters(data) {
+        if is_tab {
+            result.extend(std::iter::repeat_n(' ', ch as usize));
+            is_tab = false;
+        } else if ch == DLAC_TAB {
+            is_tab = true;
+        } else if ch == DLAC_ETX {
+            break;
+        } else {
+            result.push(DLAC_ALPHABET[ch as usize] as char);
+        }
+    }
+
+    result
+}
+
+/// Encode a string as DLAC, terminated with an end of text character
+pub fn encode_dlac(value: &str) -> Result<Vec<u8>, GDL90Error> {
+    let mut bits = 0_u32;
+    let mut bit_count = 0;
+    let mut result = vec![];
+
+    let characters = value.chars().map(|c| {
+        DLAC_ALPHABET[1..]
+            .iter()
+            .position(|&d| d as char == c && c != '\t')
+            .map(|idx| idx as u32 + 1)
+            .ok_or_else(|| GDL90Error::InvalidParam(format!("No DLAC character for {c:?}").into()))
+    });
+
+    for ch in characters.chain(std::iter::once(Ok(DLAC_ETX as u32))) {
+        bits = (bits << 6) | ch?;
+        bit_count += 6;
+
+        while bit_count >= 8 {
+            bit_count -= 8;
+            result.push((bits >> bit_count) as u8);
+        }
+    }
+
+    if bit_count > 0 {
+        result.push((bits << (8 - bit_count)) as u8);
+    }
+
+    Ok(result)
+}
+
+fn dlac_characters(data: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    (0..data.len() * 8 / 6).map(|idx| {
+        let bit = idx * 6;
+        let byte = bit / 8;
+        let word = u16::from_be_bytes([data[byte], *data.get(byte + 1).unwrap_or(&0)]);
+        ((word >> (10 - bit % 8)) & 0x3F) as u8
+    })
+}
+
+/// Text product record
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextProduct {
+    record_type: RecordType,
+    station: String,
+    issue_time: Option<IssueTime>,
+    text: String,
+}
+
+impl TextProduct {
+    pub fn get_record_type(&self) -> &RecordType {
+        &self.record_type
+    }
+
+    /// Station identifier, e.g. `KIND`
+    pub fn get_station(&self) -> &str {
+        &self.station
+    }
+
+    /// Issue time, if the record has one
+    pub fn get_issue_time(&self) -> Option<IssueTime> {
+        self.issue_time
+    }
+
+    /// Report body text
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl TryFrom<&str> for TextProduct {
+    type Error = GDL90Error;
+
+    fn try_from(record: &str) -> Result<Self, Self::Error> {
+        let mut tokens = record.split_whitespace();
+
+        let (Some(record_type), Some(station)) = (tokens.next(), tokens.next()) else {
+            return Err(GDL90Error::Parse(
+                format!("Text record is missing a type or station: {record:?}").into(),
+            ));
+        };
+
+        let mut remainder = record.trim_start();
+        for token in [record_type, station] {
+            remainder = remainder[token.len()..].trim_start();
+        }
+
+        let issue_time = tokens.next().and_then(IssueTime::parse);
+        if let (Some(_), Some(token)) = (issue_time, remainder.split_whitespace().next()) {
+            remainder = remainder[token.len()..].trim_start();
+        }
+
+        Ok(TextProduct {
+            record_type: RecordType::from(record_type),
+            station: station.to_owned(),
+            issue_time,
+            text: remainder.trim_end().to_owned(),
+        })
+    }
+}
+
+/// Text product record type
+#[derive(Debug, PartialEq, Clone)]
+pub enum RecordType {
+    Metar,
+    Speci,
+    Taf,
+    Pirep,
+    Winds,
+    Unknown(String),
+}
+
+impl From<&str> for RecordType {
+    fn from(value: &str) -> Self {
+        // e.g. TAF.AMD for an amended TAF
+        match value.split('.').next() {
+            Some("METAR") => RecordType::Metar,
+            Some("SPECI") => RecordType::Speci,
+            Some("TAF") => RecordType::Taf,
+            Some("PIREP") => RecordType::Pirep,
+            Some("WINDS") => RecordType::Winds,
+            _ => RecordType::Unknown(value.to_owned()),
+        }
+    }
+}
+
+/// Issue time (UTC)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct IssueTime {
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+impl IssueTime {
+    /// Parse a `DDHHMMZ` time group
+    fn parse(value: &str) -> Option<Self> {
+        let digits = value.strip_suffix('Z')?;
+        if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        Some(IssueTime {
+            day: digits[0..2].parse().ok()?,
+            hour: digits[2..4].parse().ok()?,
+            minute: digits[4..6].parse().ok()?,
+        })
+    }
+}
+
+/// Decode the text records in a generic textual data product [Apdu]
+pub fn decode_text(apdu: &Apdu) -> Result<Vec<TextProduct>, GDL90Error> {
+    let product_id = apdu.get_header().get_product_id();
+    if product_id != TEXT_PRODUCT_ID {
+        return Err(GDL90Error::Parse(
+            format!("Product {product_id} is not a text product").into(),
+        ));
+    }
+
+    decode_dlac(apdu.get_payload())
+        .split(RECORD_SEPARATOR)
+        .filter(|record| !record.trim().is_empty())
+        .map(TextProduct::try_from)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_dlac, decode_text, encode_dlac, IssueTime, RecordType, TextProduct};
+    use crate::fisb::test::build_apdu;
+
+    #[test]
+    fn test_decode_dlac() {
+        // "METAR" followed by end of text
+        let data = [0x34, 0x55, 0x01, 0x48, 0x00];
+        assert_eq!(decode_dlac(&data), "METAR");
+    }
+
+    #[test]
+    fn test_decode_dlac_tab() {
+        // "A", tab of 3 spaces, "B"
+        let data = [0x05, 0xC0, 0xC2];
+        assert_eq!(decode_dlac(&data), "A   B");
+    }
+
+    #[test]
+    fn test_dlac_round_trip() {
+        let text = "TAF KIND 261720Z 2618/2724 14010KT P6SM\nFM270200 VRB03KT=";
+        assert_eq!(decode_dlac(&encode_dlac(text).unwrap()), text);
+        assert!(encode_dlac("lower case").is_err());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let text = "METAR KIND 261654Z 14008KT 10SM FEW250 22/13 A3012=\x1E\
+                    TAF.AMD KBOS 261720Z 2618/2724 VRB03KT P6SM SKC=\x1E\
+                    WINDS ABI 261200Z  FT 3000 6000\n   1312 1716+08\x1E";
+        let apdu = build_apdu(413, &encode_dlac(text).unwrap());
+
+        let records = decode_text(&apdu).unwrap();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(
+            records[0],
+            TextProduct {
+                record_type: RecordType::Metar,
+                station: "KIND".to_owned(),
+                issue_time: Some(IssueTime {
+                    day: 26,
+                    hour: 16,
+                    minute: 54
+                }),
+                text: "14008KT 10SM FEW250 22/13 A3012=".to_owned(),
+            }
+        );
+        assert_eq!(records[1].get_record_type(), &RecordType::Taf);
+        assert_eq!(records[1].get_station(), "KBOS");
+        assert_eq!(records[2].get_record_type(), &RecordType::Winds);
+        assert_eq!(records[2].get_text(), "FT 3000 6000\n   1312 1716+08");
+    }
+
+    #[test]
+    fn test_record_without_time() {
+        let record = TextProduct::try_from("PIREP KIND UA /OV IND").unwrap();
+
+        assert_eq!(record.get_record_type(), &RecordType::Pirep);
+        assert_eq!(record.get_issue_time(), None);
+        assert_eq!(record.get_text(), "UA /OV IND");
+        assert!(TextProduct::try_from("METAR").is_err());
+    }
+
+    #[test]
+    fn test_not_text() {
+        assert!(decode_text(&build_apdu(63, &[0x00])).is_err());
+    }
+}
@@ -72,6 +72,24 @@ impl HeartBeat {
         self.time_stamp_msb = (value & 1_u32 << 17) != 0;
         self.time_stamp = (value as u16).swap_bytes();
     }
+
+    /// Number of uplink messages received in the previous second (5 bits)
+    pub fn get_uplink_count(&self) -> u8 {
+        (self.message_counts >> 11) as u8
+    }
+
+    pub fn set_uplink_count(&mut self, value: u8) {
+        self.message_counts = (self.message_counts & 0x07FF) | ((value as u16 & 0x1F) << 11);
+    }
+
+    /// Number of basic and long messages received in the previous second (10 bits)
+    pub fn get_basic_long_count(&self) -> u16 {
+        self.message_counts & 0x03FF
+    }
+
+    pub fn set_basic_long_count(&mut self, value: u16) {
+        self.message_counts = (self.message_counts & !0x03FF) | (value & 0x03FF);
+    }
 }
 
 #[cfg(test)]
@@ -132,4 +150,17 @@ mod test {
 
         assert_eq!(53236, heartbeat.get_time_stamp());
     }
+
+    #[test]
+    fn test_message_counts() {
+        let data = [0x81, 0x41, 0xDB, 0xD0, 0x08, 0x02];
+        let mut heartbeat = HeartBeat::try_from(&data[..]).unwrap();
+
+        assert_eq!(heartbeat.get_uplink_count(), 1);
+        assert_eq!(heartbeat.get_basic_long_count(), 2);
+
+        heartbeat.set_uplink_count(31);
+        heartbeat.set_basic_long_count(1023);
+        assert_eq!(heartbeat.message_counts, 0xFBFF);
+    }
 }
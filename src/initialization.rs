@@ -0,0 +1,112 @@
+//! Initialization message
+use deku::prelude::*;
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct Initialization {
+    #[deku(bits = "1")]
+    reserved1: bool,
+
+    #[deku(bits = "1")]
+    audio_test: bool,
+
+    #[deku(bits = "4")]
+    reserved2: u8,
+
+    #[deku(bits = "1")]
+    audio_inhibit: bool,
+
+    #[deku(bits = "1")]
+    cdti_ok: bool,
+
+    #[deku(bits = "6")]
+    reserved3: u8,
+
+    #[deku(bits = "1")]
+    csa_audio_disable: bool,
+
+    #[deku(bits = "1")]
+    csa_disable: bool,
+}
+
+impl Initialization {
+    /// Configuration byte 1 (Audio Test)
+    pub fn get_audio_test(&self) -> bool {
+        self.audio_test
+    }
+
+    /// Configuration byte 1 (Audio Test)
+    pub fn set_audio_test(&mut self, value: bool) {
+        self.audio_test = value;
+    }
+
+    /// Configuration byte 1 (Audio Inhibit)
+    pub fn get_audio_inhibit(&self) -> bool {
+        self.audio_inhibit
+    }
+
+    /// Configuration byte 1 (Audio Inhibit)
+    pub fn set_audio_inhibit(&mut self, value: bool) {
+        self.audio_inhibit = value;
+    }
+
+    /// Configuration byte 1 (CDTI OK)
+    pub fn get_cdti_ok(&self) -> bool {
+        self.cdti_ok
+    }
+
+    /// Configuration byte 1 (CDTI OK)
+    pub fn set_cdti_ok(&mut self, value: bool) {
+        self.cdti_ok = value;
+    }
+
+    /// Configuration byte 2 (CSA Audio Disable)
+    pub fn get_csa_audio_disable(&self) -> bool {
+        self.csa_audio_disable
+    }
+
+    /// Configuration byte 2 (CSA Audio Disable)
+    pub fn set_csa_audio_disable(&mut self, value: bool) {
+        self.csa_audio_disable = value;
+    }
+
+    /// Configuration byte 2 (CSA Disable)
+    pub fn get_csa_disable(&self) -> bool {
+        self.csa_disable
+    }
+
+    /// Configuration byte 2 (CSA Disable)
+    pub fn set_csa_disable(&mut self, value: bool) {
+        self.csa_disable = value;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::Initialization;
+
+    #[test]
+    fn test_encode_decode() {
+        let data = [0x43, 0x02];
+        let init = Initialization::try_from(&data[..]).unwrap();
+
+        assert!(init.get_audio_test());
+        assert!(init.get_audio_inhibit());
+        assert!(init.get_cdti_ok());
+        assert!(init.get_csa_audio_disable());
+        assert!(!init.get_csa_disable());
+
+        let mut new_init = Initialization::default();
+        new_init.set_audio_test(true);
+        new_init.set_audio_inhibit(true);
+        new_init.set_cdti_ok(true);
+        new_init.set_csa_audio_disable(true);
+
+        let encode = new_init.to_bytes().unwrap();
+
+        assert_eq!(init, new_init);
+        assert_eq!(&data[..], &encode[..]);
+    }
+}
@@ -0,0 +1,239 @@
+//! Levil attitude and heading reference system (AHRS) message, also sent by Stratux
+//!
+//! Reference: <https://github.com/cyoung/stratux/blob/master/notes/app-vendor-integration.md>
+use deku::prelude::*;
+
+const INVALID: i16 = 0x7FFF;
+const PRESSURE_ALTITUDE_INVALID: u16 = 0xFFFF;
+const PRESSURE_ALTITUDE_OFFSET: i32 = 5000;
+
+/// Levil AHRS (message ID 0x4C, sub ID 0x45)
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big", magic = b"E")]
+pub struct LevilAHRS {
+    version: [u8; 2],
+    roll: i16,
+    pitch: i16,
+    heading: i16,
+    slip_skid: i16,
+    yaw_rate: i16,
+    g_load: i16,
+    indicated_airspeed: i16,
+    pressure_altitude: u16,
+    vertical_speed: i16,
+    reserved: i16,
+}
+
+impl Default for LevilAHRS {
+    fn default() -> Self {
+        Self {
+            version: [1, 1],
+            roll: INVALID,
+            pitch: INVALID,
+            heading: INVALID,
+            slip_skid: INVALID,
+            yaw_rate: INVALID,
+            g_load: INVALID,
+            indicated_airspeed: INVALID,
+            pressure_altitude: PRESSURE_ALTITUDE_INVALID,
+            vertical_speed: INVALID,
+            reserved: INVALID,
+        }
+    }
+}
+
+impl LevilAHRS {
+    /// Message version
+    pub fn get_version(&self) -> [u8; 2] {
+        self.version
+    }
+
+    /// Roll (in degrees, right wing down positive)
+    pub fn get_roll(&self) -> Option<f32> {
+        get_tenths(self.roll)
+    }
+
+    /// Roll (in degrees, right wing down positive)
+    pub fn set_roll(&mut self, value: Option<f32>) {
+        self.roll = tenths(value);
+    }
+
+    /// Pitch (in degrees, nose up positive)
+    pub fn get_pitch(&self) -> Option<f32> {
+        get_tenths(self.pitch)
+    }
+
+    /// Pitch (in degrees, nose up positive)
+    pub fn set_pitch(&mut self, value: Option<f32>) {
+        self.pitch = tenths(value);
+    }
+
+    /// Heading (in degrees)
+    pub fn get_heading(&self) -> Option<f32> {
+        get_tenths(self.heading)
+    }
+
+    /// Heading (in degrees)
+    pub fn set_heading(&mut self, value: Option<f32>) {
+        self.heading = tenths(value);
+    }
+
+    /// Slip/skid (in degrees)
+    pub fn get_slip_skid(&self) -> Option<f32> {
+        get_tenths(self.slip_skid)
+    }
+
+    /// Slip/skid (in degrees)
+    pub fn set_slip_skid(&mut self, value: Option<f32>) {
+        self.slip_skid = tenths(value);
+    }
+
+    /// Yaw rate (in degrees per second)
+    pub fn get_yaw_rate(&self) -> Option<f32> {
+        get_tenths(self.yaw_rate)
+    }
+
+    /// Yaw rate (in degrees per second)
+    pub fn set_yaw_rate(&mut self, value: Option<f32>) {
+        self.yaw_rate = tenths(value);
+    }
+
+    /// Load factor (in G)
+    pub fn get_g_load(&self) -> Option<f32> {
+        get_tenths(self.g_load)
+    }
+
+    /// Load factor (in G)
+    pub fn set_g_load(&mut self, value: Option<f32>) {
+        self.g_load = tenths(value);
+    }
+
+    /// Indicated airspeed (in knots)
+    pub fn get_indicated_airspeed(&self) -> Option<i16> {
+        match self.indicated_airspeed {
+            INVALID => None,
+            value => Some(value),
+        }
+    }
+
+    /// Indicated airspeed (in knots)
+    pub fn set_indicated_airspeed(&mut self, value: Option<i16>) {
+        self.indicated_airspeed = value.map_or(INVALID, |value| value.min(INVALID - 1));
+    }
+
+    /// Pressure altitude (in feet, -5000 to 60534)
+    pub fn get_pressure_altitude(&self) -> Option<i32> {
+        match self.pressure_altitude {
+            PRESSURE_ALTITUDE_INVALID => None,
+            value => Some(value as i32 - PRESSURE_ALTITUDE_OFFSET),
+        }
+    }
+
+    /// Pressure altitude (in feet, -5000 to 60534)
+    pub fn set_pressure_altitude(&mut self, value: Option<i32>) {
+        self.pressure_altitude = value.map_or(PRESSURE_ALTITUDE_INVALID, |value| {
+            (value + PRESSURE_ALTITUDE_OFFSET).clamp(0, PRESSURE_ALTITUDE_INVALID as i32 - 1) as u16
+        });
+    }
+
+    /// Vertical speed (in feet per minute)
+    pub fn get_vertical_speed(&self) -> Option<i16> {
+        match self.vertical_speed {
+            INVALID => None,
+            value => Some(value),
+        }
+    }
+
+    /// Vertical speed (in feet per minute)
+    pub fn set_vertical_speed(&mut self, value: Option<i16>) {
+        self.vertical_speed = value.map_or(INVALID, |value| value.min(INVALID - 1));
+    }
+}
+
+fn get_tenths(value: i16) -> Option<f32> {
+    match value {
+        INVALID => None,
+        value => Some(value as f32 / 10.),
+    }
+}
+
+fn tenths(value: Option<f32>) -> i16 {
+    value.map_or(INVALID, |value| {
+        (value * 10.)
+            .round()
+            .clamp(i16::MIN as f32, (INVALID - 1) as f32) as i16
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::LevilAHRS;
+    use crate::{message::create_message, payload::Payload};
+
+    #[test]
+    fn test_encode_decode() {
+        let data = [
+            0x45, 0x01, 0x01, // Sub ID and version
+            0xFF, 0x9C, // Roll -10.0
+            0x00, 0x32, // Pitch 5.0
+            0x0D, 0x89, // Heading 346.5
+            0xFF, 0xFB, // Slip/skid -0.5
+            0x00, 0x1E, // Yaw rate 3.0
+            0x00, 0x0B, // G load 1.1
+            0x00, 0x6E, // IAS 110
+            0x2E, 0xE0, // Pressure altitude 7000
+            0xFE, 0x0C, // Vertical speed -500
+            0x7F, 0xFF, // Reserved
+        ];
+
+        let ahrs = LevilAHRS::try_from(&data[..]).unwrap();
+
+        assert_eq!(ahrs.get_version(), [1, 1]);
+        assert_eq!(ahrs.get_roll(), Some(-10.));
+        assert_eq!(ahrs.get_pitch(), Some(5.));
+        assert_eq!(ahrs.get_heading(), Some(346.5));
+        assert_eq!(ahrs.get_slip_skid(), Some(-0.5));
+        assert_eq!(ahrs.get_yaw_rate(), Some(3.));
+        assert_eq!(ahrs.get_g_load(), Some(1.1));
+        assert_eq!(ahrs.get_indicated_airspeed(), Some(110));
+        assert_eq!(ahrs.get_pressure_altitude(), Some(7000));
+        assert_eq!(ahrs.get_vertical_speed(), Some(-500));
+
+        let mut new_ahrs = LevilAHRS::default();
+        new_ahrs.set_roll(Some(-10.));
+        new_ahrs.set_pitch(Some(5.));
+        new_ahrs.set_heading(Some(346.5));
+        new_ahrs.set_slip_skid(Some(-0.5));
+        new_ahrs.set_yaw_rate(Some(3.));
+        new_ahrs.set_g_load(Some(1.1));
+        new_ahrs.set_indicated_airspeed(Some(110));
+        new_ahrs.set_pressure_altitude(Some(7000));
+        new_ahrs.set_vertical_speed(Some(-500));
+
+        assert_eq!(ahrs, new_ahrs);
+        assert_eq!(&new_ahrs.to_bytes().unwrap()[..], &data[..]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let ahrs = LevilAHRS::default();
+
+        assert_eq!(ahrs.get_roll(), None);
+        assert_eq!(ahrs.get_pitch(), None);
+        assert_eq!(ahrs.get_heading(), None);
+        assert_eq!(ahrs.get_slip_skid(), None);
+        assert_eq!(ahrs.get_yaw_rate(), None);
+        assert_eq!(ahrs.get_g_load(), None);
+        assert_eq!(ahrs.get_indicated_airspeed(), None);
+        assert_eq!(ahrs.get_pressure_altitude(), None);
+        assert_eq!(ahrs.get_vertical_speed(), None);
+
+        let message = create_message(&Payload::LevilAHRS(ahrs)).unwrap();
+        assert!(matches!(
+            message.get_payload(),
+            Ok(Payload::LevilAHRS(ahrs)) if ahrs == LevilAHRS::default()
+        ));
+    }
+}
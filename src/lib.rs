@@ -40,10 +40,28 @@ if let Ok(Payload::HeartBeat(payload)) = message.get_payload() {
 <https://www.faa.gov/sites/faa.gov/files/air_traffic/technology/adsb/archival/GDL90_Public_ICD_RevA.PDF>
 */
 
+#[cfg(feature = "async")]
+pub mod codec;
 pub mod error;
 pub mod extended;
+pub mod fisb;
 pub mod geometric;
 pub mod heartbeat;
+pub mod initialization;
+pub mod levil;
 pub mod message;
+pub mod passthrough;
 pub mod payload;
+pub mod pcap;
+pub mod recording;
+pub mod registry;
 pub mod report;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod stratux;
+pub mod terrain;
+pub mod traffic;
+pub mod uat;
+pub mod uplink;
+#[cfg(feature = "serde")]
+mod views;
@@ -4,14 +4,26 @@ use crate::{
     extended::ExtendedX65SubMessage,
     geometric::OwnershipGeometricAltitude,
     heartbeat::HeartBeat,
+    initialization::Initialization,
+    levil::LevilAHRS,
+    passthrough::{BasicUatReport, LongUatReport},
     payload::{
-        Payload, EXTENDED_X65, HEARTBEAT_ID, OWNERSHIP_GEOMETRIC_ALTITUDE, OWNERSHIP_REPORT_ID,
-        TRAFFIC_REPORT_ID,
+        Payload, BASIC_UAT_REPORT_ID, EXTENDED_X65, HEARTBEAT_ID, HEIGHT_ABOVE_TERRAIN_ID,
+        INITIALIZATION_ID, LEVIL_AHRS_ID, LEVIL_AHRS_SUB_ID, LONG_UAT_REPORT_ID,
+        OWNERSHIP_GEOMETRIC_ALTITUDE, OWNERSHIP_REPORT_ID, STRATUX_HEARTBEAT_ID, STRATUX_STATUS_ID,
+        STRATUX_STATUS_SUB_ID, TRAFFIC_REPORT_ID, UPLINK_DATA_ID,
     },
+    registry::PayloadRegistry,
     report::Report,
+    stratux::{StratuxHeartbeat, StratuxStatus},
+    terrain::HeightAboveTerrain,
+    uplink::UplinkData,
 };
 use std::iter::once;
 
+/// Largest number of buffered bytes, after the opening flag, before an unterminated frame is dropped
+const MAX_FRAME_LENGTH: usize = 1024;
+
 const CRC16_TABLE: [u16; 256] = create_crc_table();
 
 const fn create_crc_table() -> [u16; 256] {
@@ -41,6 +53,10 @@ pub struct GDL90Message {
 }
 
 impl GDL90Message {
+    pub fn get_message_id(&self) -> u8 {
+        self.message_id
+    }
+
     /// Encode a [GDL90Message] as bytes
     pub fn encode(&self) -> Vec<u8> {
         let mut result = vec![];
@@ -73,17 +89,51 @@ impl GDL90Message {
     pub fn get_payload(&self) -> Result<Payload, GDL90Error> {
         match self.message_id {
             HEARTBEAT_ID => Ok(Payload::HeartBeat(HeartBeat::try_from(&self.data[..])?)),
+            INITIALIZATION_ID => Ok(Payload::Initialization(Initialization::try_from(
+                &self.data[..],
+            )?)),
+            UPLINK_DATA_ID => Ok(Payload::UplinkData(UplinkData::try_from(&self.data[..])?)),
             OWNERSHIP_REPORT_ID => Ok(Payload::OwnershipReport(Report::try_from(&self.data[..])?)),
             TRAFFIC_REPORT_ID => Ok(Payload::TrafficReport(Report::try_from(&self.data[..])?)),
             OWNERSHIP_GEOMETRIC_ALTITUDE => Ok(Payload::OwnershipGeometricAltitude(
                 OwnershipGeometricAltitude::try_from(&self.data[..])?,
             )),
+            HEIGHT_ABOVE_TERRAIN_ID => Ok(Payload::HeightAboveTerrain(
+                HeightAboveTerrain::try_from(&self.data[..])?,
+            )),
+            BASIC_UAT_REPORT_ID => Ok(Payload::BasicUatReport(BasicUatReport::try_from(
+                &self.data[..],
+            )?)),
+            LONG_UAT_REPORT_ID => Ok(Payload::LongUatReport(LongUatReport::try_from(
+                &self.data[..],
+            )?)),
             EXTENDED_X65 => Ok(Payload::ExtendedX65(ExtendedX65SubMessage::try_from(
                 &self.data[..],
             )?)),
+            STRATUX_HEARTBEAT_ID => Ok(Payload::StratuxHeartbeat(StratuxHeartbeat::try_from(
+                &self.data[..],
+            )?)),
+            STRATUX_STATUS_ID if self.data.first() == Some(&STRATUX_STATUS_SUB_ID) => Ok(
+                Payload::StratuxStatus(StratuxStatus::try_from(&self.data[..])?),
+            ),
+            LEVIL_AHRS_ID if self.data.first() == Some(&LEVIL_AHRS_SUB_ID) => {
+                Ok(Payload::LevilAHRS(LevilAHRS::try_from(&self.data[..])?))
+            }
             _ => Ok(Payload::Unknown(self.message_id, self.data.clone())),
         }
     }
+
+    /// Decode [Payload] from message data, using the registered decoders for vendor
+    /// specific message IDs before the built-in payload types
+    pub fn get_payload_with_registry(
+        &self,
+        registry: &PayloadRegistry,
+    ) -> Result<Payload, GDL90Error> {
+        match registry.decode(self.message_id, &self.data) {
+            Some(result) => result.map(Payload::Custom),
+            None => self.get_payload(),
+        }
+    }
 }
 
 /// Create a [GDL90Message] from a u8 slice
@@ -95,6 +145,89 @@ pub fn read_message(data: &[u8]) -> Result<GDL90Message, GDL90Error> {
     }
 }
 
+/// Stateful decoder that splits a raw byte stream into [GDL90Message] values
+///
+/// Data may be pushed in arbitrary chunks, e.g. partial frames or several frames per
+/// UDP datagram. Garbage between frames is discarded and a frame that fails to decode
+/// is reported as an error without losing the frame that follows it.
+///
+/// ```rust
+/// use gdl90codec::message::Gdl90Framer;
+///
+/// let mut framer = Gdl90Framer::new();
+///
+/// framer.push(&[0x7E, 0x00, 0x81, 0x41, 0xDB]);
+/// assert!(framer.next().is_none());
+///
+/// framer.push(&[0xD0, 0x08, 0x02, 0xB3, 0x8B, 0x7E]);
+/// assert!(framer.next().unwrap().is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct Gdl90Framer {
+    buffer: Vec<u8>,
+}
+
+impl Gdl90Framer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append received bytes to the internal buffer
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Number of bytes buffered but not yet decoded
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Discard any buffered bytes
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+impl Iterator for Gdl90Framer {
+    type Item = Result<GDL90Message, GDL90Error>;
+
+    /// Decode the next complete frame, or `None` if more data is needed
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (consumed, result) = decode_frame(&self.buffer);
+            self.buffer.drain(..consumed);
+
+            if result.is_some() || consumed == 0 {
+                return result;
+            }
+        }
+    }
+}
+
+/// Scan `buffer` for the next flag delimited frame.
+///
+/// Returns the number of bytes that can be discarded and the decoded message if a
+/// complete frame was found. The closing flag is never consumed so that it can also
+/// serve as the opening flag of the next frame.
+pub(crate) fn decode_frame(buffer: &[u8]) -> (usize, Option<Result<GDL90Message, GDL90Error>>) {
+    let Some(start) = buffer.iter().position(|&b| b == 0x7e) else {
+        return (buffer.len(), None);
+    };
+
+    match buffer[start + 1..].iter().position(|&b| b == 0x7e) {
+        // Back-to-back flags, the second flag opens the next frame
+        Some(0) => (start + 1, None),
+        Some(length) => {
+            let end = start + 1 + length;
+            (end, Some(read_message(&buffer[start..=end])))
+        }
+        None if buffer.len() - start > MAX_FRAME_LENGTH => {
+            (start + 1, Some(Err(GDL90Error::IncorrectlyFormatted)))
+        }
+        None => (start, None),
+    }
+}
+
 /// Create a [GDL90Message] for a [Payload]
 pub fn create_message(payload: &Payload) -> Result<GDL90Message, GDL90Error> {
     let data = payload.to_bytes()?;
@@ -166,8 +299,10 @@ fn unescape(data: &[u8]) -> Vec<u8> {
 
 #[cfg(test)]
 mod test {
-    use super::checksum;
-    use crate::message::read_message;
+    use super::{checksum, Gdl90Framer};
+    use crate::{error::GDL90Error, message::read_message};
+
+    const HEARTBEAT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8B\x7E";
 
     #[test]
     fn test_check_sum() {
@@ -187,4 +322,67 @@ mod test {
         let encoded = message.encode();
         assert_eq!(&data[..], &encoded[..]);
     }
+
+    #[test]
+    fn test_framer_partial_frames() {
+        let mut framer = Gdl90Framer::new();
+
+        for chunk in HEARTBEAT.chunks(3) {
+            assert!(framer.next().is_none());
+            framer.push(chunk);
+        }
+
+        let message = framer.next().unwrap().unwrap();
+        assert_eq!(message, read_message(HEARTBEAT).unwrap());
+        assert!(framer.next().is_none());
+    }
+
+    #[test]
+    fn test_framer_multiple_frames() {
+        let mut framer = Gdl90Framer::new();
+        framer.push(HEARTBEAT);
+        framer.push(HEARTBEAT);
+        framer.push(&HEARTBEAT[1..]); // Shared flag between frames
+
+        assert_eq!(3, framer.by_ref().filter(|m| m.is_ok()).count());
+        assert_eq!(1, framer.buffered_len());
+    }
+
+    #[test]
+    fn test_framer_resync() {
+        let mut framer = Gdl90Framer::new();
+        framer.push(&[0x01, 0x02, 0x7E, 0x7E]);
+        framer.push(&HEARTBEAT[..HEARTBEAT.len() - 3]);
+        framer.push(&[0x00, 0x00, 0x7E]); // Corrupt checksum
+        framer.push(&[0x55]); // Too short
+        framer.push(HEARTBEAT);
+
+        assert!(matches!(
+            framer.next(),
+            Some(Err(GDL90Error::ChecksumMismatch(_, _)))
+        ));
+        assert!(matches!(
+            framer.next(),
+            Some(Err(GDL90Error::IncorrectlyFormatted))
+        ));
+        assert!(matches!(framer.next(), Some(Ok(_))));
+        assert!(framer.next().is_none());
+    }
+
+    #[test]
+    fn test_framer_unterminated_frame() {
+        let mut framer = Gdl90Framer::new();
+        framer.push(&[0x7E]);
+        framer.push(&[0x00; 2048]);
+
+        assert!(matches!(
+            framer.next(),
+            Some(Err(GDL90Error::IncorrectlyFormatted))
+        ));
+        assert!(framer.next().is_none());
+        assert_eq!(0, framer.buffered_len());
+
+        framer.push(HEARTBEAT);
+        assert!(matches!(framer.next(), Some(Ok(_))));
+    }
 }
@@ -0,0 +1,157 @@
+//! Pass-through reports (Basic and Long UAT ADS-B reports)
+use crate::{
+    error::GDL90Error,
+    uat::UatAdsbPayload,
+    uplink::{decode_time_of_reception, encode_time_of_reception, TIME_OF_RECEPTION_INVALID},
+};
+use deku::prelude::*;
+
+/// Length of a basic UAT ADS-B payload, in bytes
+pub const BASIC_UAT_PAYLOAD_LENGTH: usize = 18;
+
+/// Length of a long UAT ADS-B payload, in bytes
+pub const LONG_UAT_PAYLOAD_LENGTH: usize = 34;
+
+/// Basic UAT report (message ID 30)
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct BasicUatReport {
+    #[deku(bits = "24", endian = "little")]
+    time_of_reception: u32,
+
+    payload: [u8; BASIC_UAT_PAYLOAD_LENGTH],
+}
+
+impl Default for BasicUatReport {
+    fn default() -> Self {
+        Self {
+            time_of_reception: TIME_OF_RECEPTION_INVALID,
+            payload: [0; BASIC_UAT_PAYLOAD_LENGTH],
+        }
+    }
+}
+
+impl BasicUatReport {
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn get_time_of_reception(&self) -> Option<u32> {
+        decode_time_of_reception(self.time_of_reception)
+    }
+
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn set_time_of_reception(&mut self, value: Option<u32>) {
+        self.time_of_reception = encode_time_of_reception(value);
+    }
+
+    /// Basic UAT ADS-B payload
+    pub fn get_payload(&self) -> &[u8; BASIC_UAT_PAYLOAD_LENGTH] {
+        &self.payload
+    }
+
+    /// Basic UAT ADS-B payload
+    pub fn set_payload(&mut self, value: [u8; BASIC_UAT_PAYLOAD_LENGTH]) {
+        self.payload = value;
+    }
+
+    /// Decode the payload elements of the UAT ADS-B payload
+    pub fn get_adsb_payload(&self) -> Result<UatAdsbPayload, GDL90Error> {
+        UatAdsbPayload::try_from(&self.payload[..])
+    }
+}
+
+/// Long UAT report (message ID 31)
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct LongUatReport {
+    #[deku(bits = "24", endian = "little")]
+    time_of_reception: u32,
+
+    payload: [u8; LONG_UAT_PAYLOAD_LENGTH],
+}
+
+impl Default for LongUatReport {
+    fn default() -> Self {
+        Self {
+            time_of_reception: TIME_OF_RECEPTION_INVALID,
+            payload: [0; LONG_UAT_PAYLOAD_LENGTH],
+        }
+    }
+}
+
+impl LongUatReport {
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn get_time_of_reception(&self) -> Option<u32> {
+        decode_time_of_reception(self.time_of_reception)
+    }
+
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn set_time_of_reception(&mut self, value: Option<u32>) {
+        self.time_of_reception = encode_time_of_reception(value);
+    }
+
+    /// Long UAT ADS-B payload
+    pub fn get_payload(&self) -> &[u8; LONG_UAT_PAYLOAD_LENGTH] {
+        &self.payload
+    }
+
+    /// Long UAT ADS-B payload
+    pub fn set_payload(&mut self, value: [u8; LONG_UAT_PAYLOAD_LENGTH]) {
+        self.payload = value;
+    }
+
+    /// Decode the payload elements of the UAT ADS-B payload
+    pub fn get_adsb_payload(&self) -> Result<UatAdsbPayload, GDL90Error> {
+        UatAdsbPayload::try_from(&self.payload[..])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::{BasicUatReport, LongUatReport, LONG_UAT_PAYLOAD_LENGTH};
+    use crate::{
+        message::{create_message, read_message},
+        payload::Payload,
+    };
+
+    #[test]
+    fn test_basic_encode_decode() {
+        let data = [
+            0x10, 0x27, 0x00, 0x00, 0xAB, 0xCD, 0xEF, 0x35, 0x70, 0xA3, 0xFF, 0x4C, 0x88, 0x2C,
+            0x0F, 0x90, 0x0C, 0x80, 0x00, 0x00, 0x00,
+        ];
+        let report = BasicUatReport::try_from(&data[..]).unwrap();
+
+        assert_eq!(report.get_time_of_reception(), Some(10000));
+        assert_eq!(report.get_payload()[..], data[3..]);
+
+        let mut new_report = BasicUatReport::default();
+        new_report.set_time_of_reception(Some(10000));
+        new_report.set_payload(data[3..].try_into().unwrap());
+
+        let encode = new_report.to_bytes().unwrap();
+
+        assert_eq!(report, new_report);
+        assert_eq!(&data[..], &encode[..]);
+    }
+
+    #[test]
+    fn test_long_round_trip() {
+        let mut report = LongUatReport::default();
+        assert_eq!(report.get_time_of_reception(), None);
+
+        report.set_payload([0x7D; LONG_UAT_PAYLOAD_LENGTH]);
+
+        let encoded = create_message(&Payload::LongUatReport(report))
+            .unwrap()
+            .encode();
+        let message = read_message(&encoded[..]).unwrap();
+
+        if let Ok(Payload::LongUatReport(payload)) = message.get_payload() {
+            assert_eq!(payload.get_time_of_reception(), None);
+            assert_eq!(payload.get_payload(), &[0x7D; LONG_UAT_PAYLOAD_LENGTH]);
+        } else {
+            panic!("Unexpected payload type");
+        }
+    }
+}
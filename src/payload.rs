@@ -1,47 +1,95 @@
 //! Message payload
 use crate::{
-    extended::ExtendedX65SubMessage, geometric::OwnershipGeometricAltitude, heartbeat::HeartBeat,
+    error::GDL90Error,
+    extended::ExtendedX65SubMessage,
+    geometric::OwnershipGeometricAltitude,
+    heartbeat::HeartBeat,
+    initialization::Initialization,
+    levil::LevilAHRS,
+    passthrough::{BasicUatReport, LongUatReport},
+    registry::CustomPayload,
     report::Report,
+    stratux::{StratuxHeartbeat, StratuxStatus},
+    terrain::HeightAboveTerrain,
+    uplink::UplinkData,
 };
-use deku::{DekuContainerWrite, DekuError};
+use deku::DekuContainerWrite;
 
 /// Message payload
 #[derive(Debug)]
 pub enum Payload {
     HeartBeat(HeartBeat),
+    Initialization(Initialization),
+    UplinkData(UplinkData),
     OwnershipReport(Report),
     TrafficReport(Report),
     OwnershipGeometricAltitude(OwnershipGeometricAltitude),
+    HeightAboveTerrain(HeightAboveTerrain),
+    BasicUatReport(BasicUatReport),
+    LongUatReport(LongUatReport),
     ExtendedX65(ExtendedX65SubMessage),
+    StratuxHeartbeat(StratuxHeartbeat),
+    StratuxStatus(StratuxStatus),
+    LevilAHRS(LevilAHRS),
+    /// Vendor specific payload, see [crate::registry]
+    Custom(Box<dyn CustomPayload>),
     Unknown(u8, Vec<u8>),
 }
 
 pub(crate) const HEARTBEAT_ID: u8 = 0;
+pub(crate) const INITIALIZATION_ID: u8 = 2;
+pub(crate) const UPLINK_DATA_ID: u8 = 7;
 pub(crate) const OWNERSHIP_REPORT_ID: u8 = 10;
 pub(crate) const TRAFFIC_REPORT_ID: u8 = 20;
 pub(crate) const OWNERSHIP_GEOMETRIC_ALTITUDE: u8 = 11;
+pub(crate) const HEIGHT_ABOVE_TERRAIN_ID: u8 = 9;
+pub(crate) const BASIC_UAT_REPORT_ID: u8 = 30;
+pub(crate) const LONG_UAT_REPORT_ID: u8 = 31;
 pub(crate) const EXTENDED_X65: u8 = 0x65;
+pub(crate) const STRATUX_HEARTBEAT_ID: u8 = 0xCC;
+pub(crate) const STRATUX_STATUS_ID: u8 = 0x53;
+pub(crate) const STRATUX_STATUS_SUB_ID: u8 = 0x58;
+pub(crate) const LEVIL_AHRS_ID: u8 = 0x4C;
+pub(crate) const LEVIL_AHRS_SUB_ID: u8 = 0x45;
 
 impl Payload {
     pub(crate) fn get_message_id(&self) -> u8 {
         match self {
             Payload::HeartBeat(_) => HEARTBEAT_ID,
+            Payload::Initialization(_) => INITIALIZATION_ID,
+            Payload::UplinkData(_) => UPLINK_DATA_ID,
             Payload::OwnershipReport(_) => OWNERSHIP_REPORT_ID,
             Payload::TrafficReport(_) => TRAFFIC_REPORT_ID,
             Payload::OwnershipGeometricAltitude(_) => OWNERSHIP_GEOMETRIC_ALTITUDE,
+            Payload::HeightAboveTerrain(_) => HEIGHT_ABOVE_TERRAIN_ID,
+            Payload::BasicUatReport(_) => BASIC_UAT_REPORT_ID,
+            Payload::LongUatReport(_) => LONG_UAT_REPORT_ID,
             Payload::ExtendedX65(_) => EXTENDED_X65,
+            Payload::StratuxHeartbeat(_) => STRATUX_HEARTBEAT_ID,
+            Payload::StratuxStatus(_) => STRATUX_STATUS_ID,
+            Payload::LevilAHRS(_) => LEVIL_AHRS_ID,
+            Payload::Custom(entity) => entity.get_message_id(),
             Payload::Unknown(msg_id, _) => *msg_id,
         }
     }
 
-    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>, DekuError> {
-        match self {
-            Payload::HeartBeat(entity) => entity.to_bytes(),
-            Payload::OwnershipReport(entity) => entity.to_bytes(),
-            Payload::TrafficReport(entity) => entity.to_bytes(),
-            Payload::OwnershipGeometricAltitude(entity) => entity.to_bytes(),
-            Payload::ExtendedX65(entity) => entity.to_bytes(),
-            Payload::Unknown(_, data) => Ok(data.clone()),
-        }
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>, GDL90Error> {
+        Ok(match self {
+            Payload::HeartBeat(entity) => entity.to_bytes()?,
+            Payload::Initialization(entity) => entity.to_bytes()?,
+            Payload::UplinkData(entity) => entity.to_bytes()?,
+            Payload::OwnershipReport(entity) => entity.to_bytes()?,
+            Payload::TrafficReport(entity) => entity.to_bytes()?,
+            Payload::OwnershipGeometricAltitude(entity) => entity.to_bytes()?,
+            Payload::HeightAboveTerrain(entity) => entity.to_bytes()?,
+            Payload::BasicUatReport(entity) => entity.to_bytes()?,
+            Payload::LongUatReport(entity) => entity.to_bytes()?,
+            Payload::ExtendedX65(entity) => entity.to_bytes()?,
+            Payload::StratuxHeartbeat(entity) => entity.to_bytes()?,
+            Payload::StratuxStatus(entity) => entity.to_bytes()?,
+            Payload::LevilAHRS(entity) => entity.to_bytes()?,
+            Payload::Custom(entity) => entity.to_bytes()?,
+            Payload::Unknown(_, data) => data.clone(),
+        })
     }
 }
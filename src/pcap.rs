@@ -0,0 +1,676 @@
+//! Import GDL90 frames from pcap and pcapng captures
+//!
+//! [PcapReader] walks the packets of a capture, extracts the payloads of UDP datagrams
+//! sent to a port (4000 by default) and splits them into GDL90 frames. Ethernet, Linux
+//! cooked (SLL and SLL2), BSD loopback and raw IP link types are supported, over IPv4 or
+//! IPv6. Fragmented IP packets are skipped.
+//!
+//! ```rust,no_run
+//! use std::fs::File;
+//! use gdl90codec::pcap::PcapReader;
+//!
+//! let mut reader = PcapReader::new(File::open("capture.pcapng").unwrap()).unwrap();
+//! reader.set_port(43211);
+//!
+//! for result in reader {
+//!     match result {
+//!         Ok((timestamp, message)) => println!("{:?} {:?}", timestamp, message.get_payload()),
+//!         Err(err) => eprintln!("{:?}", err),
+//!     }
+//! }
+//! ```
+use crate::{
+    error::{read_or_eof, GDL90Error},
+    message::{GDL90Message, Gdl90Framer},
+};
+use std::{
+    collections::VecDeque,
+    io::Read,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_PORT: u16 = 4000;
+
+const PCAP_MICROSECONDS: u32 = 0xA1B2C3D4;
+const PCAP_NANOSECONDS: u32 = 0xA1B23C4D;
+const PCAPNG_SECTION_HEADER: u32 = 0x0A0D0D0A;
+const PCAPNG_BYTE_ORDER: u32 = 0x1A2B3C4D;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const PCAPNG_OPTION_END: u16 = 0;
+const PCAPNG_OPTION_TSRESOL: u16 = 9;
+const PCAPNG_OPTION_TSOFFSET: u16 = 14;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LOOP: u32 = 108;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+const ETHERTYPE_VLAN: [u16; 2] = [0x8100, 0x88A8];
+
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Largest block or packet accepted, to guard against corrupt lengths
+const MAX_LENGTH: usize = 0x100_0000;
+
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u32,
+    /// Timestamp units per second
+    resolution: u64,
+    /// Seconds added to timestamps
+    offset: i64,
+}
+
+#[derive(Debug)]
+enum Format {
+    Pcap {
+        big_endian: bool,
+        interface: Interface,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// Reads `(capture_timestamp, GDL90Message)` pairs from a pcap or pcapng capture
+///
+/// Frames which fail to decode are returned as errors and reading continues with the
+/// next frame, while errors reading the capture itself end the iteration.
+#[derive(Debug)]
+pub struct PcapReader<R: Read> {
+    reader: R,
+    format: Format,
+    port: u16,
+    framer: Gdl90Framer,
+    pending: VecDeque<Result<(SystemTime, GDL90Message), GDL90Error>>,
+    done: bool,
+}
+
+impl<R: Read> PcapReader<R> {
+    /// Read the capture header, detecting the pcap or pcapng format
+    pub fn new(mut reader: R) -> Result<Self, GDL90Error> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        let format = if u32::from_le_bytes(magic) == PCAPNG_SECTION_HEADER {
+            let mut header = [0; 8];
+            header[..4].copy_from_slice(&magic);
+            reader.read_exact(&mut header[4..])?;
+
+            let mut big_endian = false;
+            read_block_body(&mut reader, header, &mut big_endian)?;
+
+            Format::PcapNg {
+                big_endian,
+                interfaces: vec![],
+            }
+        } else {
+            let big_endian = match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+                (PCAP_MICROSECONDS | PCAP_NANOSECONDS, _) => false,
+                (_, PCAP_MICROSECONDS | PCAP_NANOSECONDS) => true,
+                _ => return Err(GDL90Error::Parse("Not a pcap or pcapng capture".into())),
+            };
+
+            // Version, time zone, significant figures, snapshot length and link type
+            let mut header = [0; 20];
+            reader.read_exact(&mut header)?;
+
+            Format::Pcap {
+                big_endian,
+                interface: Interface {
+                    link_type: read_u32(&header, 16, big_endian).ok_or_else(truncated)? & 0xFFFF,
+                    resolution: match read_u32(&magic, 0, big_endian) {
+                        Some(PCAP_NANOSECONDS) => 1_000_000_000,
+                        _ => 1_000_000,
+                    },
+                    offset: 0,
+                },
+            }
+        };
+
+        Ok(Self {
+            reader,
+            format,
+            port: DEFAULT_PORT,
+            framer: Gdl90Framer::new(),
+            pending: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    /// UDP destination port of the GDL90 datagrams
+    pub fn set_port(&mut self, port: u16) {
+        self.port = port;
+    }
+
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    /// Read the next packet, `None` at the end of the capture
+    fn read_packet(&mut self) -> Result<Option<(SystemTime, u32, Vec<u8>)>, GDL90Error> {
+        match &mut self.format {
+            Format::Pcap {
+                big_endian,
+                interface,
+            } => {
+                let mut header = [0; 16];
+                if !read_or_eof(&mut self.reader, &mut header)? {
+                    return Ok(None);
+                }
+
+                let seconds = read_u32(&header, 0, *big_endian).ok_or_else(truncated)?;
+                let fraction = read_u32(&header, 4, *big_endian).ok_or_else(truncated)?;
+                let length = read_u32(&header, 8, *big_endian).ok_or_else(truncated)? as usize;
+
+                if length > MAX_LENGTH {
+                    return Err(GDL90Error::Parse("Invalid pcap packet length".into()));
+                }
+
+                let mut data = vec![0; length];
+                self.reader.read_exact(&mut data)?;
+
+                let timestamp = seconds as u64 * interface.resolution + fraction as u64;
+                Ok(Some((
+                    to_time(timestamp, interface)?,
+                    interface.link_type,
+                    data,
+                )))
+            }
+            Format::PcapNg {
+                big_endian,
+                interfaces,
+            } => loop {
+                let Some((block_type, block)) = read_block(&mut self.reader, big_endian)? else {
+                    return Ok(None);
+                };
+
+                match block_type {
+                    // A new section has its own interfaces
+                    PCAPNG_SECTION_HEADER => interfaces.clear(),
+                    PCAPNG_INTERFACE_DESCRIPTION => {
+                        interfaces.push(read_interface_description(&block, *big_endian)?);
+                    }
+                    PCAPNG_ENHANCED_PACKET => {
+                        let field = |offset| read_u32(&block, offset, *big_endian);
+                        let (Some(interface_id), Some(high), Some(low), Some(length)) =
+                            (field(0), field(4), field(8), field(12))
+                        else {
+                            return Err(truncated());
+                        };
+
+                        let interface = interfaces
+                            .get(interface_id as usize)
+                            .ok_or(GDL90Error::Parse("Unknown pcapng interface".into()))?;
+                        let data = block.get(20..20 + length as usize).ok_or_else(truncated)?;
+
+                        let timestamp = (high as u64) << 32 | low as u64;
+                        return Ok(Some((
+                            to_time(timestamp, interface)?,
+                            interface.link_type,
+                            data.to_vec(),
+                        )));
+                    }
+                    // Other blocks, including simple packet blocks which have no timestamp
+                    _ => {}
+                }
+            },
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapReader<R> {
+    type Item = Result<(SystemTime, GDL90Message), GDL90Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.done {
+            match self.read_packet() {
+                Ok(Some((timestamp, link_type, data))) => {
+                    let Some(payload) = udp_payload(link_type, &data, self.port) else {
+                        continue;
+                    };
+
+                    // Each datagram carries whole frames
+                    self.framer.clear();
+                    self.framer.push(payload);
+                    self.pending.extend(
+                        self.framer
+                            .by_ref()
+                            .map(|result| result.map(|message| (timestamp, message))),
+                    );
+                }
+                Ok(None) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        self.pending.pop_front()
+    }
+}
+
+fn truncated() -> GDL90Error {
+    GDL90Error::Parse("Truncated capture".into())
+}
+
+fn read_u16(data: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?.try_into().ok()?;
+
+    Some(if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?.try_into().ok()?;
+
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+/// Read the next pcapng block type and body, `None` at the end of the capture
+fn read_block(
+    reader: &mut impl Read,
+    big_endian: &mut bool,
+) -> Result<Option<(u32, Vec<u8>)>, GDL90Error> {
+    let mut header = [0; 8];
+    if !read_or_eof(reader, &mut header)? {
+        return Ok(None);
+    }
+
+    read_block_body(reader, header, big_endian).map(Some)
+}
+
+/// Read a pcapng block body following its type and length
+///
+/// Section header blocks set `big_endian` from their byte order magic.
+fn read_block_body(
+    reader: &mut impl Read,
+    header: [u8; 8],
+    big_endian: &mut bool,
+) -> Result<(u32, Vec<u8>), GDL90Error> {
+    let mut body = vec![];
+
+    // The section header block type reads the same in both byte orders
+    if header[..4] == PCAPNG_SECTION_HEADER.to_le_bytes() {
+        let mut byte_order = [0; 4];
+        reader.read_exact(&mut byte_order)?;
+
+        *big_endian = match (
+            u32::from_le_bytes(byte_order),
+            u32::from_be_bytes(byte_order),
+        ) {
+            (PCAPNG_BYTE_ORDER, _) => false,
+            (_, PCAPNG_BYTE_ORDER) => true,
+            _ => return Err(GDL90Error::Parse("Invalid pcapng byte order".into())),
+        };
+        body.extend(byte_order);
+    }
+
+    let block_type = read_u32(&header, 0, *big_endian).ok_or_else(truncated)?;
+    let length = read_u32(&header, 4, *big_endian).ok_or_else(truncated)? as usize;
+
+    // Type, length, body and trailing length, padded to 32 bits
+    if length < 12 + body.len() || !length.is_multiple_of(4) || length > MAX_LENGTH {
+        return Err(GDL90Error::Parse("Invalid pcapng block length".into()));
+    }
+
+    let start = body.len();
+    body.resize(length - 8, 0);
+    reader.read_exact(&mut body[start..])?;
+    body.truncate(length - 12);
+
+    Ok((block_type, body))
+}
+
+fn read_interface_description(block: &[u8], big_endian: bool) -> Result<Interface, GDL90Error> {
+    let mut interface = Interface {
+        link_type: read_u16(block, 0, big_endian).ok_or_else(truncated)? as u32,
+        resolution: 1_000_000,
+        offset: 0,
+    };
+
+    // Options follow the link type, reserved and snapshot length fields
+    let mut options = block.get(8..).unwrap_or_default();
+
+    while let (Some(code), Some(length)) = (
+        read_u16(options, 0, big_endian),
+        read_u16(options, 2, big_endian),
+    ) {
+        let length = length as usize;
+        let Some(value) = options.get(4..4 + length) else {
+            break;
+        };
+
+        match (code, value) {
+            (PCAPNG_OPTION_END, _) => break,
+            (PCAPNG_OPTION_TSRESOL, [resolution]) => {
+                // Negative power of 10, or of 2 if the most significant bit is set
+                interface.resolution = if resolution & 0x80 == 0 {
+                    10u64.checked_pow(*resolution as u32)
+                } else {
+                    1u64.checked_shl((resolution & 0x7F) as u32)
+                }
+                .ok_or(GDL90Error::Parse(
+                    "Invalid pcapng timestamp resolution".into(),
+                ))?;
+            }
+            (PCAPNG_OPTION_TSOFFSET, _) => {
+                if let Ok(bytes) = value.try_into() {
+                    interface.offset = if big_endian {
+                        i64::from_be_bytes(bytes)
+                    } else {
+                        i64::from_le_bytes(bytes)
+                    };
+                }
+            }
+            _ => {}
+        }
+
+        options = options
+            .get(4 + length.next_multiple_of(4)..)
+            .unwrap_or_default();
+    }
+
+    Ok(interface)
+}
+
+/// Convert a timestamp in interface units to the capture time
+fn to_time(timestamp: u64, interface: &Interface) -> Result<SystemTime, GDL90Error> {
+    let seconds = timestamp / interface.resolution;
+    let nanoseconds =
+        (timestamp % interface.resolution) as u128 * 1_000_000_000 / interface.resolution as u128;
+    let time = UNIX_EPOCH.checked_add(Duration::new(seconds, nanoseconds as u32));
+
+    let offset = Duration::from_secs(interface.offset.unsigned_abs());
+    if interface.offset >= 0 {
+        time.and_then(|time| time.checked_add(offset))
+    } else {
+        time.and_then(|time| time.checked_sub(offset))
+    }
+    .ok_or(GDL90Error::Parse("Invalid capture timestamp".into()))
+}
+
+/// The payload of a UDP datagram sent to `port`, if the packet is one
+fn udp_payload(link_type: u32, data: &[u8], port: u16) -> Option<&[u8]> {
+    let (ethertype, ip) = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut offset = 12;
+            while ETHERTYPE_VLAN.contains(&read_u16(data, offset, true)?) {
+                offset += 4;
+            }
+            (read_u16(data, offset, true)?, data.get(offset + 2..)?)
+        }
+        LINKTYPE_LINUX_SLL => (read_u16(data, 14, true)?, data.get(16..)?),
+        LINKTYPE_LINUX_SLL2 => (read_u16(data, 0, true)?, data.get(20..)?),
+        LINKTYPE_NULL | LINKTYPE_LOOP => {
+            // The address family is in the capturing host's byte order for NULL
+            let family = match read_u32(data, 0, link_type == LINKTYPE_LOOP)? {
+                family if family > 0xFFFF => family.swap_bytes(),
+                family => family,
+            };
+            let ethertype = match family {
+                2 => ETHERTYPE_IPV4,
+                24 | 28 | 30 => ETHERTYPE_IPV6,
+                _ => return None,
+            };
+            (ethertype, data.get(4..)?)
+        }
+        LINKTYPE_RAW => match data.first()? >> 4 {
+            4 => (ETHERTYPE_IPV4, data),
+            6 => (ETHERTYPE_IPV6, data),
+            _ => return None,
+        },
+        LINKTYPE_IPV4 => (ETHERTYPE_IPV4, data),
+        LINKTYPE_IPV6 => (ETHERTYPE_IPV6, data),
+        _ => return None,
+    };
+
+    let udp = match ethertype {
+        ETHERTYPE_IPV4 => ipv4_payload(ip)?,
+        ETHERTYPE_IPV6 => ipv6_payload(ip)?,
+        _ => return None,
+    };
+
+    // Source port, destination port, length and checksum
+    let length = read_u16(udp, 4, true)? as usize;
+    if read_u16(udp, 2, true)? != port || length < 8 {
+        return None;
+    }
+
+    udp.get(8..length)
+}
+
+/// The UDP segment of an unfragmented IPv4 packet
+fn ipv4_payload(ip: &[u8]) -> Option<&[u8]> {
+    let header_length = (ip.first()? & 0x0F) as usize * 4;
+    let total_length = read_u16(ip, 2, true)? as usize;
+
+    // More fragments flag and fragment offset
+    if read_u16(ip, 6, true)? & 0x3FFF != 0 || *ip.get(9)? != IP_PROTOCOL_UDP {
+        return None;
+    }
+
+    ip.get(header_length..total_length)
+}
+
+/// The UDP segment of an unfragmented IPv6 packet
+fn ipv6_payload(ip: &[u8]) -> Option<&[u8]> {
+    let payload_length = read_u16(ip, 4, true)? as usize;
+    let mut next_header = *ip.get(6)?;
+    let mut payload = ip.get(40..40 + payload_length)?;
+
+    // Hop-by-hop, routing and destination options extension headers
+    while matches!(next_header, 0 | 43 | 60) {
+        next_header = *payload.first()?;
+        payload = payload.get((*payload.get(1)? as usize + 1) * 8..)?;
+    }
+
+    (next_header == IP_PROTOCOL_UDP).then_some(payload)
+}
+
+#[cfg(test)]
+mod test {
+    use super::PcapReader;
+    use crate::{error::GDL90Error, payload::Payload};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const HEARTBEAT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8B\x7E";
+    const CORRUPT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8C\x7E";
+
+    fn ipv4_udp(port: u16, payload: &[u8]) -> Vec<u8> {
+        let udp_length = (8 + payload.len()) as u16;
+        let total_length = 20 + udp_length;
+
+        [
+            &[0x45, 0x00][..],
+            &total_length.to_be_bytes(),
+            &[0, 0, 0, 0, 64, 17, 0, 0, 192, 168, 10, 1, 192, 168, 10, 255],
+            &43211u16.to_be_bytes(),
+            &port.to_be_bytes(),
+            &udp_length.to_be_bytes(),
+            &[0, 0],
+            payload,
+        ]
+        .concat()
+    }
+
+    fn ethernet(ip: &[u8]) -> Vec<u8> {
+        [&[0xFF; 6][..], &[0x02; 6], &[0x08, 0x00], ip].concat()
+    }
+
+    fn pcap_big_endian_nanoseconds(packets: &[(u32, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = [
+            &0xA1B23C4Du32.to_be_bytes()[..],
+            &[0, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF],
+            &1u32.to_be_bytes(),
+        ]
+        .concat();
+
+        for (seconds, nanoseconds, packet) in packets {
+            let length = (packet.len() as u32).to_be_bytes();
+            data.extend(seconds.to_be_bytes());
+            data.extend(nanoseconds.to_be_bytes());
+            data.extend(length);
+            data.extend(length);
+            data.extend(packet);
+        }
+
+        data
+    }
+
+    fn pcapng_block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let padding = body.len().next_multiple_of(4) - body.len();
+        let length = ((12 + body.len() + padding) as u32).to_le_bytes();
+
+        [
+            &block_type.to_le_bytes()[..],
+            &length,
+            body,
+            &vec![0; padding],
+            &length,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_pcap() {
+        let capture = pcap_big_endian_nanoseconds(&[
+            (
+                1_700_000_000,
+                500,
+                ethernet(&ipv4_udp(4000, &[HEARTBEAT, CORRUPT, HEARTBEAT].concat())),
+            ),
+            (1_700_000_001, 0, ethernet(&ipv4_udp(4001, HEARTBEAT))),
+            (1_700_000_002, 0, ethernet(&[0x45, 0x00])),
+        ]);
+
+        let mut reader = PcapReader::new(&capture[..]).unwrap();
+        let timestamp = UNIX_EPOCH + Duration::new(1_700_000_000, 500);
+
+        let (time, message) = reader.next().unwrap().unwrap();
+        assert_eq!(time, timestamp);
+        assert!(matches!(message.get_payload(), Ok(Payload::HeartBeat(_))));
+        assert!(matches!(
+            reader.next(),
+            Some(Err(GDL90Error::ChecksumMismatch(_, _)))
+        ));
+        assert_eq!(reader.next().unwrap().unwrap().0, timestamp);
+        assert!(reader.next().is_none());
+
+        let mut reader = PcapReader::new(&capture[..]).unwrap();
+        reader.set_port(4001);
+        assert_eq!(
+            reader.next().unwrap().unwrap().0,
+            UNIX_EPOCH + Duration::from_secs(1_700_000_001)
+        );
+        assert!(reader.next().is_none());
+
+        // Truncated capture
+        let mut reader = PcapReader::new(&capture[..capture.len() - 1]).unwrap();
+        reader.set_port(4002);
+        assert!(matches!(reader.next(), Some(Err(GDL90Error::Io(_)))));
+        assert!(reader.next().is_none());
+
+        assert!(PcapReader::new(&b"GDL90REC"[..]).is_err());
+    }
+
+    #[test]
+    fn test_pcapng() {
+        let section_header = [&0x1A2B3C4Du32.to_le_bytes()[..], &[1, 0, 0, 0], &[0xFF; 8]].concat();
+        // Raw IP link type, millisecond resolution and one hour offset
+        let interface = [
+            &[101, 0, 0, 0, 0xFF, 0xFF, 0, 0][..],
+            &[9, 0, 1, 0, 3, 0, 0, 0],
+            &[14, 0, 8, 0],
+            &3600i64.to_le_bytes(),
+            &[0, 0, 0, 0],
+        ]
+        .concat();
+        let packet = ipv4_udp(4000, HEARTBEAT);
+        let timestamp = 1_700_000_000_123u64;
+        let enhanced_packet = [
+            &0u32.to_le_bytes()[..],
+            &((timestamp >> 32) as u32).to_le_bytes(),
+            &(timestamp as u32).to_le_bytes(),
+            &(packet.len() as u32).to_le_bytes(),
+            &(packet.len() as u32).to_le_bytes(),
+            &packet,
+        ]
+        .concat();
+
+        let capture = [
+            pcapng_block(0x0A0D0D0A, &section_header),
+            pcapng_block(1, &interface),
+            pcapng_block(5, &[0; 4]),
+            pcapng_block(6, &enhanced_packet),
+        ]
+        .concat();
+
+        let results = PcapReader::new(&capture[..])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].0,
+            UNIX_EPOCH + Duration::from_millis(timestamp) + Duration::from_secs(3600)
+        );
+        assert!(matches!(
+            results[0].1.get_payload(),
+            Ok(Payload::HeartBeat(_))
+        ));
+
+        // Timestamps beyond the range of the system time, in seconds
+        let seconds_interface = [
+            &[101, 0, 0, 0, 0xFF, 0xFF, 0, 0][..],
+            &[9, 0, 1, 0, 0, 0, 0, 0],
+            &[0, 0, 0, 0],
+        ]
+        .concat();
+        let oversized_packet = [
+            &0u32.to_le_bytes()[..],
+            &u32::MAX.to_le_bytes(),
+            &u32::MAX.to_le_bytes(),
+            &enhanced_packet[12..],
+        ]
+        .concat();
+        let capture = [
+            pcapng_block(0x0A0D0D0A, &section_header),
+            pcapng_block(1, &seconds_interface),
+            pcapng_block(6, &oversized_packet),
+        ]
+        .concat();
+        assert!(matches!(
+            PcapReader::new(&capture[..]).unwrap().next(),
+            Some(Err(GDL90Error::Parse(_)))
+        ));
+
+        // Packets need an interface description
+        let capture = [
+            pcapng_block(0x0A0D0D0A, &section_header),
+            pcapng_block(6, &enhanced_packet),
+        ]
+        .concat();
+        assert!(matches!(
+            PcapReader::new(&capture[..]).unwrap().next(),
+            Some(Err(GDL90Error::Parse(_)))
+        ));
+    }
+}
@@ -0,0 +1,406 @@
+//! Recording and timed replay of GDL90 streams
+//!
+//! A recording is a binary file made of an 8 byte header followed by one record per
+//! message:
+//!
+//! | Field     | Size     | Description                                                |
+//! |-----------|----------|------------------------------------------------------------|
+//! | Magic     | 8        | `GDL90REC`                                                 |
+//! | Timestamp | 8        | Microseconds since the start of the recording, little endian |
+//! | Length    | 2        | Frame length in bytes, little endian                       |
+//! | Frame     | Length   | Encoded frame, as returned by [GDL90Message::encode]       |
+//!
+//! The timestamp, length and frame fields repeat until the end of the file.
+//!
+//! ```rust
+//! use std::ops::ControlFlow;
+//! use std::time::Duration;
+//! use gdl90codec::heartbeat::HeartBeat;
+//! use gdl90codec::message::create_message;
+//! use gdl90codec::payload::Payload;
+//! use gdl90codec::recording::{read_recording, Recorder, Replay};
+//!
+//! let message = create_message(&Payload::HeartBeat(HeartBeat::default())).unwrap();
+//!
+//! let mut recorder = Recorder::new(Vec::new()).unwrap();
+//! recorder.record_at(Duration::ZERO, &message).unwrap();
+//! recorder.record_at(Duration::from_millis(10), &message).unwrap();
+//! let data = recorder.into_inner();
+//!
+//! let mut replay = Replay::new(read_recording(&data[..]).unwrap());
+//! replay.set_speed(2.0).unwrap();
+//!
+//! let mut count = 0;
+//! replay.play(|_message| {
+//!     count += 1;
+//!     ControlFlow::<()>::Continue(())
+//! });
+//! assert_eq!(count, 2);
+//! ```
+use crate::{
+    error::{read_or_eof, GDL90Error},
+    message::{read_message, GDL90Message},
+};
+use std::{
+    io::{self, Read, Write},
+    net::{ToSocketAddrs, UdpSocket},
+    ops::ControlFlow,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+const MAGIC: &[u8; 8] = b"GDL90REC";
+
+/// Writes timestamped messages in the recording format
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    writer: W,
+    start: Instant,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Write the recording header, the recording starts now
+    pub fn new(mut writer: W) -> Result<Self, GDL90Error> {
+        writer.write_all(MAGIC)?;
+
+        Ok(Self {
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    /// Record a message with the time elapsed since the recorder was created
+    pub fn record(&mut self, message: &GDL90Message) -> Result<(), GDL90Error> {
+        self.record_at(self.start.elapsed(), message)
+    }
+
+    /// Record a message with an explicit time since the start of the recording
+    pub fn record_at(
+        &mut self,
+        timestamp: Duration,
+        message: &GDL90Message,
+    ) -> Result<(), GDL90Error> {
+        let frame = message.encode();
+        let length = u16::try_from(frame.len()).map_err(|_| {
+            GDL90Error::InvalidParam(format!("Frame of {} bytes is too long", frame.len()).into())
+        })?;
+        let timestamp = u64::try_from(timestamp.as_micros()).unwrap_or(u64::MAX);
+
+        self.writer.write_all(&timestamp.to_le_bytes())?;
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(&frame)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), GDL90Error> {
+        Ok(self.writer.flush()?)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads timestamped messages in the recording format
+///
+/// Yields `(timestamp, message)` pairs, the timestamp being the time since the start of
+/// the recording.
+#[derive(Debug)]
+pub struct RecordingReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> RecordingReader<R> {
+    /// Read and check the recording header
+    pub fn new(mut reader: R) -> Result<Self, GDL90Error> {
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+
+        if &magic != MAGIC {
+            return Err(GDL90Error::Parse("Not a GDL90 recording".into()));
+        }
+
+        Ok(Self { reader })
+    }
+
+    fn read_record(&mut self) -> Result<Option<(Duration, GDL90Message)>, GDL90Error> {
+        let mut timestamp = [0; 8];
+
+        // End of file is only expected between records
+        if !read_or_eof(&mut self.reader, &mut timestamp)? {
+            return Ok(None);
+        }
+
+        let mut length = [0; 2];
+        self.reader.read_exact(&mut length)?;
+
+        let mut frame = vec![0; u16::from_le_bytes(length) as usize];
+        self.reader.read_exact(&mut frame)?;
+
+        let timestamp = Duration::from_micros(u64::from_le_bytes(timestamp));
+        Ok(Some((timestamp, read_message(&frame)?)))
+    }
+}
+
+impl<R: Read> Iterator for RecordingReader<R> {
+    type Item = Result<(Duration, GDL90Message), GDL90Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Read a whole recording into memory
+pub fn read_recording(reader: impl Read) -> Result<Vec<(Duration, GDL90Message)>, GDL90Error> {
+    RecordingReader::new(reader)?.collect()
+}
+
+/// Replays recorded messages with their original timing
+#[derive(Debug)]
+pub struct Replay {
+    records: Vec<(Duration, GDL90Message)>,
+    speed: f64,
+    looping: bool,
+}
+
+impl Replay {
+    /// Replay `(timestamp, message)` pairs, in timestamp order, at normal speed
+    pub fn new(records: Vec<(Duration, GDL90Message)>) -> Self {
+        Self {
+            records,
+            speed: 1.,
+            looping: false,
+        }
+    }
+
+    /// Replay speed factor, e.g. 2.0 for twice as fast, 0.5 for half speed
+    pub fn set_speed(&mut self, speed: f64) -> Result<(), GDL90Error> {
+        if !speed.is_finite() || speed <= 0. {
+            return Err(GDL90Error::InvalidParam(
+                format!("Invalid replay speed {speed}").into(),
+            ));
+        }
+
+        self.speed = speed;
+        Ok(())
+    }
+
+    pub fn get_speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Restart from the beginning after the last message
+    ///
+    /// Each loop lasts from the first message until one average message interval after
+    /// the last, so a recording whose messages all have the same timestamp cannot loop.
+    pub fn set_looping(&mut self, looping: bool) -> Result<(), GDL90Error> {
+        if looping && self.get_loop_duration().is_zero() {
+            return Err(GDL90Error::InvalidParam(
+                "Cannot loop a recording of zero duration".into(),
+            ));
+        }
+
+        self.looping = looping;
+        Ok(())
+    }
+
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Unscaled duration of one loop
+    fn get_loop_duration(&self) -> Duration {
+        let (Some((first, _)), Some((last, _))) = (self.records.first(), self.records.last())
+        else {
+            return Duration::ZERO;
+        };
+
+        let length = last.saturating_sub(*first);
+        match self.records.len() {
+            0 | 1 => Duration::ZERO,
+            count => length + length / (count as u32 - 1),
+        }
+    }
+
+    /// Pass each message to `callback` at its scaled time, until the end of the recording
+    /// (never if looping) or until `callback` returns [ControlFlow::Break]
+    pub fn play<B>(&self, mut callback: impl FnMut(&GDL90Message) -> ControlFlow<B>) -> Option<B> {
+        let start = Instant::now();
+
+        self.play_at(|due, message| {
+            sleep((start + due).saturating_duration_since(Instant::now()));
+            callback(message)
+        })
+    }
+
+    /// Pass each message to `callback` without waiting, along with its scaled time since
+    /// the start of playback, until the end of the recording (never if looping) or until
+    /// `callback` returns [ControlFlow::Break]
+    pub fn play_at<B>(
+        &self,
+        mut callback: impl FnMut(Duration, &GDL90Message) -> ControlFlow<B>,
+    ) -> Option<B> {
+        let (first, _) = self.records.first()?;
+        let duration = self.scale(self.get_loop_duration());
+        let mut start = Duration::ZERO;
+
+        loop {
+            for (timestamp, message) in &self.records {
+                let due = start + self.scale(timestamp.saturating_sub(*first));
+
+                if let ControlFlow::Break(value) = callback(due, message) {
+                    return Some(value);
+                }
+            }
+
+            if !self.looping {
+                return None;
+            }
+
+            start += duration;
+        }
+    }
+
+    /// Send each message to `destination` at its scaled time, until the end of the
+    /// recording or an error occurs
+    pub fn play_udp(
+        &self,
+        socket: &UdpSocket,
+        destination: impl ToSocketAddrs,
+    ) -> Result<(), GDL90Error> {
+        let destination = destination
+            .to_socket_addrs()?
+            .next()
+            .ok_or(GDL90Error::Io(io::ErrorKind::AddrNotAvailable))?;
+
+        let result = self.play(
+            |message| match socket.send_to(&message.encode(), destination) {
+                Ok(_) => ControlFlow::Continue(()),
+                Err(err) => ControlFlow::Break(err),
+            },
+        );
+
+        match result {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
+        }
+    }
+
+    fn scale(&self, duration: Duration) -> Duration {
+        duration.div_f64(self.speed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_recording, Recorder, RecordingReader, Replay};
+    use crate::{
+        error::GDL90Error,
+        message::{create_message, read_message},
+        payload::Payload,
+        terrain::HeightAboveTerrain,
+    };
+    use std::{io::ErrorKind, ops::ControlFlow, time::Duration};
+
+    const HEARTBEAT: &[u8] = b"\x7E\x00\x81\x41\xDB\xD0\x08\x02\xB3\x8B\x7E";
+
+    #[test]
+    fn test_record() {
+        let heartbeat = read_message(HEARTBEAT).unwrap();
+        let mut recorder = Recorder::new(Vec::new()).unwrap();
+        recorder
+            .record_at(Duration::from_micros(0x0102), &heartbeat)
+            .unwrap();
+        let data = recorder.into_inner();
+
+        assert_eq!(
+            data,
+            [
+                b"GDL90REC".as_slice(),
+                &[0x02, 0x01, 0, 0, 0, 0, 0, 0],
+                &[HEARTBEAT.len() as u8, 0],
+                HEARTBEAT
+            ]
+            .concat()
+        );
+
+        let records = read_recording(&data[..]).unwrap();
+        assert_eq!(records, vec![(Duration::from_micros(0x0102), heartbeat)]);
+
+        assert!(matches!(
+            RecordingReader::new(&b"GDL90XXX"[..]),
+            Err(GDL90Error::Parse(_))
+        ));
+
+        // Truncated record
+        let mut reader = RecordingReader::new(&data[..data.len() - 1]).unwrap();
+        assert!(reader.next().unwrap().is_err());
+
+        // Truncated timestamp
+        let mut reader = RecordingReader::new(&data[..12]).unwrap();
+        assert!(matches!(
+            reader.next(),
+            Some(Err(GDL90Error::Io(ErrorKind::UnexpectedEof)))
+        ));
+        assert!(RecordingReader::new(&data[..8]).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_replay() {
+        let records = (0..3)
+            .map(|index| {
+                let mut terrain = HeightAboveTerrain::default();
+                terrain.set_height_above_terrain(Some(index));
+                let message = create_message(&Payload::HeightAboveTerrain(terrain)).unwrap();
+                (Duration::from_millis(100 * index as u64), message)
+            })
+            .collect();
+
+        let mut replay = Replay::new(records);
+        replay.set_speed(10.).unwrap();
+        replay.set_looping(true).unwrap();
+
+        let mut heights = vec![];
+        let mut due_times = vec![];
+        replay.play_at(|due, message| {
+            if let Ok(Payload::HeightAboveTerrain(terrain)) = message.get_payload() {
+                heights.push(terrain.get_height_above_terrain());
+                due_times.push(due);
+            }
+
+            if heights.len() == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        assert_eq!(heights, vec![Some(0), Some(1), Some(2), Some(0), Some(1)]);
+        // Ten times the speed, with a 10 ms gap between loops
+        assert_eq!(
+            due_times,
+            [0, 10, 20, 30, 40].map(Duration::from_millis).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_replay_zero_duration() {
+        let heartbeat = read_message(HEARTBEAT).unwrap();
+        let mut replay = Replay::new(vec![(Duration::from_secs(1), heartbeat)]);
+
+        assert!(matches!(
+            replay.set_looping(true),
+            Err(GDL90Error::InvalidParam(_))
+        ));
+        assert!(!replay.is_looping());
+        assert!(replay.set_speed(0.).is_err());
+        assert!(replay.set_speed(f64::INFINITY).is_err());
+
+        let mut count = 0;
+        replay.play(|_| {
+            count += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,248 @@
+//! Registry of decoders for vendor specific message IDs
+//!
+//! Applications can register a [CustomDecoder] for a message ID, or for a sub message ID
+//! of the ForeFlight extended message (ID 0x65), and decode messages with
+//! [GDL90Message::get_payload_with_registry]. Registered IDs take precedence over the
+//! built-in payload types and are returned as [Payload::Custom].
+//!
+//! ```rust
+//! use std::any::Any;
+//! use gdl90codec::error::GDL90Error;
+//! use gdl90codec::message::create_message;
+//! use gdl90codec::payload::Payload;
+//! use gdl90codec::registry::{CustomPayload, PayloadRegistry};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Battery {
+//!     percent: u8,
+//! }
+//!
+//! impl CustomPayload for Battery {
+//!     fn get_message_id(&self) -> u8 {
+//!         0x70
+//!     }
+//!
+//!     fn to_bytes(&self) -> Result<Vec<u8>, GDL90Error> {
+//!         Ok(vec![self.percent])
+//!     }
+//!
+//!     fn as_any(&self) -> &dyn Any {
+//!         self
+//!     }
+//! }
+//!
+//! let mut registry = PayloadRegistry::new();
+//! registry.register(0x70, |data: &[u8]| match data {
+//!     [percent] => Ok(Box::new(Battery { percent: *percent }) as Box<dyn CustomPayload>),
+//!     _ => Err(GDL90Error::IncorrectlyFormatted),
+//! });
+//!
+//! let message = create_message(&Payload::Custom(Box::new(Battery { percent: 80 }))).unwrap();
+//!
+//! if let Ok(Payload::Custom(payload)) = message.get_payload_with_registry(&registry) {
+//!     assert_eq!(payload.downcast_ref::<Battery>(), Some(&Battery { percent: 80 }));
+//! } else {
+//!     panic!("Unexpected payload type");
+//! }
+//! ```
+use crate::{error::GDL90Error, payload::EXTENDED_X65};
+#[cfg(doc)]
+use crate::{message::GDL90Message, payload::Payload};
+use std::{any::Any, collections::HashMap, fmt::Debug};
+
+/// Vendor specific payload
+pub trait CustomPayload: Debug + Send + Sync {
+    /// Message ID (0x65 for ForeFlight extended sub messages)
+    fn get_message_id(&self) -> u8;
+
+    /// Message data following the message ID, including the sub message ID for 0x65
+    fn to_bytes(&self) -> Result<Vec<u8>, GDL90Error>;
+
+    /// Allows the payload to be downcast to its concrete type
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl dyn CustomPayload {
+    /// Downcast to the concrete payload type
+    pub fn downcast_ref<T: CustomPayload + 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
+
+/// Decoder for a vendor specific payload
+///
+/// Implemented for closures taking the message data, which follows the message ID and
+/// includes the sub message ID for 0x65.
+pub trait CustomDecoder: Send + Sync {
+    fn decode(&self, data: &[u8]) -> Result<Box<dyn CustomPayload>, GDL90Error>;
+}
+
+impl<F> CustomDecoder for F
+where
+    F: Fn(&[u8]) -> Result<Box<dyn CustomPayload>, GDL90Error> + Send + Sync,
+{
+    fn decode(&self, data: &[u8]) -> Result<Box<dyn CustomPayload>, GDL90Error> {
+        self(data)
+    }
+}
+
+/// Decoders for vendor specific message IDs and 0x65 sub message IDs
+#[derive(Default)]
+pub struct PayloadRegistry {
+    messages: HashMap<u8, Box<dyn CustomDecoder>>,
+    extended_x65: HashMap<u8, Box<dyn CustomDecoder>>,
+}
+
+impl PayloadRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a decoder for a message ID, replacing any previous decoder
+    pub fn register(&mut self, message_id: u8, decoder: impl CustomDecoder + 'static) {
+        self.messages.insert(message_id, Box::new(decoder));
+    }
+
+    /// Register a decoder for a 0x65 sub message ID, replacing any previous decoder
+    pub fn register_extended_x65(&mut self, sub_id: u8, decoder: impl CustomDecoder + 'static) {
+        self.extended_x65.insert(sub_id, Box::new(decoder));
+    }
+
+    /// Decode message data, if a decoder is registered for it
+    pub(crate) fn decode(
+        &self,
+        message_id: u8,
+        data: &[u8],
+    ) -> Option<Result<Box<dyn CustomPayload>, GDL90Error>> {
+        let decoder = match (message_id, data.first()) {
+            (EXTENDED_X65, Some(sub_id)) if self.extended_x65.contains_key(sub_id) => {
+                self.extended_x65.get(sub_id)
+            }
+            _ => self.messages.get(&message_id),
+        };
+
+        decoder.map(|decoder| decoder.decode(data))
+    }
+}
+
+impl Debug for PayloadRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadRegistry")
+            .field("messages", &self.messages.keys())
+            .field("extended_x65", &self.extended_x65.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::any::Any;
+
+    use super::{CustomPayload, PayloadRegistry};
+    use crate::{
+        error::GDL90Error,
+        extended::ExtendedX65SubMessage,
+        message::{create_message, read_message},
+        payload::Payload,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Vendor {
+        message_id: u8,
+        data: Vec<u8>,
+    }
+
+    impl CustomPayload for Vendor {
+        fn get_message_id(&self) -> u8 {
+            self.message_id
+        }
+
+        fn to_bytes(&self) -> Result<Vec<u8>, GDL90Error> {
+            Ok(self.data.clone())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    fn decode_vendor(
+        message_id: u8,
+    ) -> impl Fn(&[u8]) -> Result<Box<dyn CustomPayload>, GDL90Error> {
+        move |data| {
+            Ok(Box::new(Vendor {
+                message_id,
+                data: data.to_vec(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_custom_message() {
+        let mut registry = PayloadRegistry::new();
+        registry.register(0x70, decode_vendor(0x70));
+
+        let payload = Payload::Custom(Box::new(Vendor {
+            message_id: 0x70,
+            data: vec![0x01, 0x7E],
+        }));
+        let encoded = create_message(&payload).unwrap().encode();
+        let message = read_message(&encoded).unwrap();
+
+        assert!(matches!(
+            message.get_payload(),
+            Ok(Payload::Unknown(0x70, _))
+        ));
+
+        if let Ok(Payload::Custom(payload)) = message.get_payload_with_registry(&registry) {
+            assert_eq!(
+                payload.downcast_ref::<Vendor>(),
+                Some(&Vendor {
+                    message_id: 0x70,
+                    data: vec![0x01, 0x7E]
+                })
+            );
+        } else {
+            panic!()
+        }
+
+        // Built-in payloads are unaffected
+        let message = create_message(&Payload::Unknown(0x71, vec![])).unwrap();
+        assert!(matches!(
+            message.get_payload_with_registry(&registry),
+            Ok(Payload::Unknown(0x71, _))
+        ));
+    }
+
+    #[test]
+    fn test_custom_extended_x65() {
+        let mut registry = PayloadRegistry::new();
+        registry.register_extended_x65(0x42, decode_vendor(0x65));
+
+        let message = create_message(&Payload::ExtendedX65(ExtendedX65SubMessage::Unknown(
+            0x42,
+            vec![0x01],
+        )))
+        .unwrap();
+
+        if let Ok(Payload::Custom(payload)) = message.get_payload_with_registry(&registry) {
+            assert_eq!(payload.get_message_id(), 0x65);
+            assert_eq!(payload.to_bytes().unwrap(), vec![0x42, 0x01]);
+        } else {
+            panic!()
+        }
+
+        let message = create_message(&Payload::ExtendedX65(ExtendedX65SubMessage::Unknown(
+            0x43,
+            vec![0x01],
+        )))
+        .unwrap();
+        assert!(matches!(
+            message.get_payload_with_registry(&registry),
+            Ok(Payload::ExtendedX65(ExtendedX65SubMessage::Unknown(
+                0x43,
+                _
+            )))
+        ));
+    }
+}
@@ -3,7 +3,7 @@ use deku::{DekuRead, DekuWrite};
 use enum_ordinalize::Ordinalize;
 use std::str::{from_utf8, Utf8Error};
 
-#[derive(DekuRead, DekuWrite, Debug, Default)]
+#[derive(DekuRead, DekuWrite, Debug, Default, Clone)]
 #[deku(bit_order = "msb", endian = "big")]
 pub struct Report {
     #[deku(bits = "4")]
@@ -207,6 +207,16 @@ impl Report {
         self.altitude = ((value + 1000) / 25) as u16;
     }
 
+    /// Mark the altitude as invalid or unavailable
+    pub(crate) fn set_altitude_invalid(&mut self) {
+        self.altitude = 0xFFF;
+    }
+
+    /// False if the altitude is invalid or unavailable
+    pub fn is_altitude_valid(&self) -> bool {
+        self.altitude != 0xFFF
+    }
+
     pub fn set_latitude(&mut self, value: f32) {
         self.latitude = lat_long_f32_to_u32(value);
     }
@@ -216,7 +226,7 @@ impl Report {
     }
 }
 
-fn lat_long_u32_to_f32(value: u32) -> f32 {
+pub(crate) fn lat_long_u32_to_f32(value: u32) -> f32 {
     let fraction = if value & 0x800000 != 0 {
         (value as i32) | !0xFFFFFF // sign-extend to 32 bits
     } else {
@@ -226,12 +236,26 @@ fn lat_long_u32_to_f32(value: u32) -> f32 {
     ((fraction * 45) as f32) / ((1 << 21) as f32)
 }
 
-fn lat_long_f32_to_u32(value: f32) -> u32 {
+pub(crate) fn lat_long_f32_to_u32(value: f32) -> u32 {
     let result = (value * ((1 << 21) as f32) / 45.) as i32;
     result as u32 & 0x00ffffff
 }
 
+/// UAT latitude, 23 bit two's complement with the same resolution as a 24 bit value
+pub(crate) fn lat_u23_to_f32(value: u32) -> f32 {
+    if value & 0x400000 != 0 {
+        lat_long_u32_to_f32(value | 0x800000)
+    } else {
+        lat_long_u32_to_f32(value)
+    }
+}
+
+pub(crate) fn lat_f32_to_u23(value: f32) -> u32 {
+    lat_long_f32_to_u32(value) & 0x7fffff
+}
+
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NIC {
     Unknown,
     Lt20_0NM,
@@ -252,6 +276,7 @@ pub enum NIC {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NACp {
     Unknown,
     Lt10_0NM,
@@ -272,6 +297,7 @@ pub enum NACp {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EmergencyPriorityCode {
     NoEmergency,
     GeneralEmergency,
@@ -292,6 +318,7 @@ pub enum EmergencyPriorityCode {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EmitterCategory {
     NoAircraftTypeInformation,
     Light,
@@ -336,18 +363,21 @@ pub enum EmitterCategory {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlightStage {
     OnGround,
     Airborne,
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReportType {
     ReportIsUpdated,
     ReportIsExtrapolated,
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HeadingType {
     NotValid,
     TrueTrackAngle,
@@ -356,6 +386,7 @@ pub enum HeadingType {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AddressType {
     ADSBWithICAOAddress,
     ADSBWithSelfAssignedAddress,
@@ -375,6 +406,7 @@ pub enum AddressType {
 }
 
 #[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TrafficAlertStatus {
     NoAlert,
     TrafficAlert,
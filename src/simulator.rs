@@ -0,0 +1,529 @@
+//! Traffic simulator (requires the `simulator` feature)
+//!
+//! A [Scenario] describes the ownship and traffic targets, and is loaded from JSON (see
+//! `scenarios/example.json`):
+//!
+//! ```json
+//! {
+//!   "ownship_rate": 1.0,
+//!   "traffic_rate": 2.0,
+//!   "ownship": {
+//!     "address": 11259375,
+//!     "callsign": "N12345",
+//!     "latitude": 51.5,
+//!     "longitude": -0.5,
+//!     "altitude": 3000,
+//!     "motion": { "StraightLine": { "track": 90.0, "ground_speed": 120.0 } }
+//!   },
+//!   "traffic": [
+//!     {
+//!       "address": 4259906,
+//!       "latitude": 51.45,
+//!       "longitude": -0.3,
+//!       "altitude": 3500,
+//!       "motion": { "Converging": { "time_to_conflict": 120.0 } }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Positions are in degrees, altitudes in feet, distances in nautical miles, speeds in
+//! knots and vertical speeds in feet per minute. A [Simulator] turns the scenario into
+//! heartbeat (1 Hz), ownship, ownship geometric altitude and traffic report messages.
+use crate::{
+    error::GDL90Error,
+    geometric::OwnershipGeometricAltitude,
+    heartbeat::HeartBeat,
+    message::{create_message, GDL90Message},
+    payload::Payload,
+    report::{
+        AddressType, EmitterCategory, FlightStage, HeadingType, NACp, Report, ReportType, NIC,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    net::{ToSocketAddrs, UdpSocket},
+    thread::sleep,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+const SECONDS_PER_DAY: u64 = 86400;
+const HEARTBEAT_INTERVAL: f64 = 1.;
+
+/// Simulated ownship and traffic
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Scenario {
+    /// Ownship report rate (in Hz)
+    #[serde(default = "default_rate")]
+    pub ownship_rate: f64,
+
+    /// Traffic report rate (in Hz)
+    #[serde(default = "default_rate")]
+    pub traffic_rate: f64,
+
+    pub ownship: Target,
+
+    #[serde(default)]
+    pub traffic: Vec<Target>,
+}
+
+fn default_rate() -> f64 {
+    1.
+}
+
+impl Scenario {
+    pub fn from_json(value: &str) -> Result<Self, GDL90Error> {
+        let scenario: Self =
+            serde_json::from_str(value).map_err(|err| GDL90Error::Parse(err.to_string().into()))?;
+
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Check that rates, orbit radii and times to conflict are positive
+    pub fn validate(&self) -> Result<(), GDL90Error> {
+        let positive = |name: &str, value: f64| {
+            if value.is_finite() && value > 0. {
+                Ok(())
+            } else {
+                Err(GDL90Error::InvalidParam(
+                    format!("{name} must be positive, not {value}").into(),
+                ))
+            }
+        };
+
+        positive("ownship_rate", self.ownship_rate)?;
+        positive("traffic_rate", self.traffic_rate)?;
+
+        for target in std::iter::once(&self.ownship).chain(&self.traffic) {
+            match target.motion {
+                Motion::Orbit { radius, .. } => positive("radius", radius)?,
+                Motion::Converging { time_to_conflict } => {
+                    positive("time_to_conflict", time_to_conflict)?
+                }
+                Motion::StraightLine { .. } => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ownship state at `time` seconds into the scenario
+    pub fn get_ownship_state(&self, time: f64) -> State {
+        self.ownship.get_state(time, None)
+    }
+
+    /// Traffic states at `time` seconds into the scenario
+    pub fn get_traffic_states(&self, time: f64) -> Vec<State> {
+        self.traffic
+            .iter()
+            .map(|target| target.get_state(time, Some(&self.ownship)))
+            .collect()
+    }
+}
+
+/// Simulated aircraft
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Target {
+    /// 24 bit participant address
+    pub address: u32,
+
+    /// Call sign (at most 8 characters)
+    #[serde(default)]
+    pub callsign: String,
+
+    #[serde(default = "default_emitter_category")]
+    pub emitter_category: EmitterCategory,
+
+    /// Initial latitude (in degrees)
+    pub latitude: f64,
+
+    /// Initial longitude (in degrees)
+    pub longitude: f64,
+
+    /// Initial pressure altitude (in feet)
+    pub altitude: f64,
+
+    pub motion: Motion,
+}
+
+fn default_emitter_category() -> EmitterCategory {
+    EmitterCategory::Light
+}
+
+impl Target {
+    fn get_state(&self, time: f64, ownship: Option<&Target>) -> State {
+        match self.motion {
+            Motion::StraightLine {
+                track,
+                ground_speed,
+                vertical_speed,
+            } => self.straight_line(time, track, ground_speed, vertical_speed),
+            Motion::Orbit {
+                radius,
+                ground_speed,
+                clockwise,
+            } => {
+                // The orbit centre is abeam the initial position, heading north
+                let direction = if clockwise { 1. } else { -1. };
+                let (center_latitude, center_longitude) =
+                    offset(self.latitude, self.longitude, 90. * direction, radius);
+
+                let start = 270. * direction;
+                let swept = (ground_speed * time / 3600. / radius).to_degrees();
+                let bearing = (start + swept * direction).rem_euclid(360.);
+                let (latitude, longitude) =
+                    offset(center_latitude, center_longitude, bearing, radius);
+
+                State {
+                    latitude,
+                    longitude,
+                    altitude: self.altitude,
+                    track: (bearing + 90. * direction).rem_euclid(360.),
+                    ground_speed,
+                    vertical_speed: 0.,
+                }
+            }
+            Motion::Converging { time_to_conflict } => {
+                let conflict = ownship
+                    .map(|ownship| ownship.get_state(time_to_conflict, None))
+                    .unwrap_or_else(|| self.straight_line(0., 0., 0., 0.));
+
+                let (distance, track) = distance_and_bearing(
+                    self.latitude,
+                    self.longitude,
+                    conflict.latitude,
+                    conflict.longitude,
+                );
+                let hours = time_to_conflict / 3600.;
+                let vertical_speed = (conflict.altitude - self.altitude) / hours / 60.;
+
+                self.straight_line(time, track, distance / hours, vertical_speed)
+            }
+        }
+    }
+
+    fn straight_line(
+        &self,
+        time: f64,
+        track: f64,
+        ground_speed: f64,
+        vertical_speed: f64,
+    ) -> State {
+        let (latitude, longitude) = offset(
+            self.latitude,
+            self.longitude,
+            track,
+            ground_speed * time / 3600.,
+        );
+
+        State {
+            latitude,
+            longitude,
+            altitude: self.altitude + vertical_speed * time / 60.,
+            track,
+            ground_speed,
+            vertical_speed,
+        }
+    }
+
+    fn create_report(&self, state: &State) -> Result<Report, GDL90Error> {
+        if self.callsign.len() > 8 {
+            return Err(GDL90Error::InvalidParam(
+                format!("Call sign {} is longer than 8 bytes", self.callsign).into(),
+            ));
+        }
+
+        let mut callsign = [b' '; 8];
+        callsign[..self.callsign.len()].copy_from_slice(self.callsign.as_bytes());
+
+        let mut report = Report::default();
+        report.participant_address = self.address & 0xFFFFFF;
+        report.set_address_type(AddressType::ADSBWithICAOAddress);
+        report.set_latitude(state.latitude as f32);
+        report.set_longitude(state.longitude as f32);
+        report.set_altitude(state.altitude.round() as i32);
+        report.set_report_type(ReportType::ReportIsUpdated);
+        report.set_flight_stage(FlightStage::Airborne);
+        report.set_navigation_integrity_category(NIC::Lt0_1NM);
+        report.set_navigation_accuracy_category_for_position(NACp::HFOMlt10mAndVFOMlt15m);
+        report.set_horizontal_velocity(Some(state.ground_speed.round() as u16));
+        // The setter takes 64 fpm units
+        report.set_vertical_velocity(Some((state.vertical_speed / 64.).round() as i16));
+        report.set_heading_type(HeadingType::TrueTrackAngle);
+        report.set_track_heading(state.track as f32);
+        report.set_emitter_category(self.emitter_category);
+        report.set_callsign(callsign);
+        Ok(report)
+    }
+}
+
+/// How a target moves
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Motion {
+    /// Constant track, ground speed and vertical speed
+    StraightLine {
+        track: f64,
+        ground_speed: f64,
+        #[serde(default)]
+        vertical_speed: f64,
+    },
+    /// Level orbit, starting northbound from the initial position
+    Orbit {
+        radius: f64,
+        ground_speed: f64,
+        #[serde(default)]
+        clockwise: bool,
+    },
+    /// Straight line reaching the ownship position and altitude after `time_to_conflict`
+    /// seconds
+    Converging { time_to_conflict: f64 },
+}
+
+/// Simulated position and velocity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct State {
+    /// Degrees
+    pub latitude: f64,
+    /// Degrees
+    pub longitude: f64,
+    /// Feet
+    pub altitude: f64,
+    /// Degrees true
+    pub track: f64,
+    /// Knots
+    pub ground_speed: f64,
+    /// Feet per minute
+    pub vertical_speed: f64,
+}
+
+/// Generates messages for a [Scenario] at the configured rates
+#[derive(Debug)]
+pub struct Simulator {
+    scenario: Scenario,
+    start_time_of_day: u64,
+    next_heartbeat: f64,
+    next_ownship: f64,
+    next_traffic: f64,
+}
+
+impl Simulator {
+    pub fn new(scenario: Scenario) -> Self {
+        let start_time_of_day = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs() % SECONDS_PER_DAY);
+
+        Self {
+            scenario,
+            start_time_of_day,
+            next_heartbeat: 0.,
+            next_ownship: 0.,
+            next_traffic: 0.,
+        }
+    }
+
+    /// Messages due at `time` seconds into the scenario, and the time of the next message
+    pub fn tick(&mut self, time: f64) -> Result<(Vec<GDL90Message>, f64), GDL90Error> {
+        let mut messages = vec![];
+
+        if time >= self.next_heartbeat {
+            let mut heartbeat = HeartBeat::default();
+            heartbeat.gps_pos_valid = true;
+            heartbeat.uat_initialized = true;
+            heartbeat.utc_ok = true;
+            heartbeat
+                .set_time_stamp(((self.start_time_of_day + time as u64) % SECONDS_PER_DAY) as u32);
+
+            messages.push(create_message(&Payload::HeartBeat(heartbeat))?);
+            self.next_heartbeat = next_time(self.next_heartbeat, time, HEARTBEAT_INTERVAL);
+        }
+
+        if time >= self.next_ownship {
+            let ownship = &self.scenario.ownship;
+            let state = self.scenario.get_ownship_state(time);
+
+            let mut geometric = OwnershipGeometricAltitude::default();
+            geometric.set_ownship_geo_altitude(state.altitude.max(0.).round() as u32);
+            geometric.set_vertical_figure_of_merit(Some(10));
+
+            messages.push(create_message(&Payload::OwnershipReport(
+                ownship.create_report(&state)?,
+            ))?);
+            messages.push(create_message(&Payload::OwnershipGeometricAltitude(
+                geometric,
+            ))?);
+            self.next_ownship = next_time(self.next_ownship, time, 1. / self.scenario.ownship_rate);
+        }
+
+        if time >= self.next_traffic {
+            let states = self.scenario.get_traffic_states(time);
+
+            for (target, state) in self.scenario.traffic.iter().zip(states) {
+                messages.push(create_message(&Payload::TrafficReport(
+                    target.create_report(&state)?,
+                ))?);
+            }
+            self.next_traffic = next_time(self.next_traffic, time, 1. / self.scenario.traffic_rate);
+        }
+
+        let next = self
+            .next_heartbeat
+            .min(self.next_ownship)
+            .min(self.next_traffic);
+
+        Ok((messages, next))
+    }
+
+    /// Send the scenario to `destination` in real time, until an error occurs
+    pub fn run(&mut self, destination: impl ToSocketAddrs) -> Result<(), GDL90Error> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.set_broadcast(true)?;
+
+        let destination = destination
+            .to_socket_addrs()?
+            .next()
+            .ok_or(GDL90Error::Io(io::ErrorKind::AddrNotAvailable))?;
+        let start = Instant::now();
+
+        loop {
+            let (messages, next) = self.tick(start.elapsed().as_secs_f64())?;
+
+            for message in messages {
+                socket.send_to(&message.encode(), destination)?;
+            }
+
+            let next = start + Duration::from_secs_f64(next);
+            sleep(next.saturating_duration_since(Instant::now()));
+        }
+    }
+}
+
+/// The next multiple of `interval` after `time`, never going backwards
+fn next_time(previous: f64, time: f64, interval: f64) -> f64 {
+    if !interval.is_finite() || interval <= 0. {
+        return f64::INFINITY;
+    }
+
+    let next = previous + interval;
+    if next > time {
+        next
+    } else {
+        ((time / interval).floor() + 1.) * interval
+    }
+}
+
+/// Flat earth offset by `distance` nautical miles on `bearing` degrees
+fn offset(latitude: f64, longitude: f64, bearing: f64, distance: f64) -> (f64, f64) {
+    let bearing = bearing.to_radians();
+    let latitude_offset = distance * bearing.cos() / 60.;
+    let longitude_offset = distance * bearing.sin() / (60. * latitude.to_radians().cos());
+
+    (latitude + latitude_offset, longitude + longitude_offset)
+}
+
+/// Flat earth distance (in nautical miles) and bearing (in degrees) between two positions
+fn distance_and_bearing(
+    latitude1: f64,
+    longitude1: f64,
+    latitude2: f64,
+    longitude2: f64,
+) -> (f64, f64) {
+    let north = (latitude2 - latitude1) * 60.;
+    let east = (longitude2 - longitude1) * 60. * latitude1.to_radians().cos();
+
+    (
+        north.hypot(east),
+        east.atan2(north).to_degrees().rem_euclid(360.),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Scenario, Simulator};
+    use crate::{error::GDL90Error, payload::Payload};
+
+    const SCENARIO: &str = include_str!("../scenarios/example.json");
+
+    #[test]
+    fn test_scenario() {
+        let scenario = Scenario::from_json(SCENARIO).unwrap();
+        assert_eq!(scenario.traffic.len(), 2);
+
+        // 120 knots east for 30 minutes
+        let ownship = scenario.get_ownship_state(1800.);
+        assert!((ownship.latitude - 51.5).abs() < 1e-9);
+        assert!((ownship.longitude - (-0.5 + 1. / 0.6225)).abs() < 0.001);
+        assert_eq!(ownship.altitude, 3000.);
+
+        // Orbit returns to the start after one circumference
+        let orbit = &scenario.traffic[0];
+        let period = 2. * std::f64::consts::PI * 2. / 90. * 3600.;
+        let states = scenario.get_traffic_states(period);
+        assert!((states[0].latitude - orbit.latitude).abs() < 1e-6);
+        assert!((states[0].longitude - orbit.longitude).abs() < 1e-6);
+        assert!((scenario.get_traffic_states(0.)[0].track).abs() < 1e-6);
+
+        // Converging traffic meets the ownship
+        let states = scenario.get_traffic_states(120.);
+        let ownship = scenario.get_ownship_state(120.);
+        assert!((states[1].latitude - ownship.latitude).abs() < 1e-6);
+        assert!((states[1].longitude - ownship.longitude).abs() < 1e-6);
+        assert!((states[1].altitude - ownship.altitude).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_scenario() {
+        for (valid, invalid) in [
+            ("\"time_to_conflict\": 120.0", "\"time_to_conflict\": 0.0"),
+            ("\"radius\": 2.0", "\"radius\": -2.0"),
+            ("\"traffic_rate\": 1.0", "\"traffic_rate\": 0.0"),
+            ("\"ownship_rate\": 1.0", "\"ownship_rate\": -1.0"),
+        ] {
+            assert!(SCENARIO.contains(valid));
+            assert!(matches!(
+                Scenario::from_json(&SCENARIO.replace(valid, invalid)),
+                Err(GDL90Error::InvalidParam(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rates() {
+        let mut scenario = Scenario::from_json(SCENARIO).unwrap();
+        scenario.traffic_rate = 2.;
+        let mut simulator = Simulator::new(scenario);
+
+        let (messages, next) = simulator.tick(0.).unwrap();
+        let payloads = messages
+            .iter()
+            .map(|message| message.get_payload().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            payloads[..],
+            [
+                Payload::HeartBeat(_),
+                Payload::OwnershipReport(_),
+                Payload::OwnershipGeometricAltitude(_),
+                Payload::TrafficReport(_),
+                Payload::TrafficReport(_),
+            ]
+        ));
+        assert_eq!(next, 0.5);
+
+        if let Payload::TrafficReport(report) = &payloads[4] {
+            assert_eq!(report.participant_address, 0x410042);
+            assert_eq!(report.get_callsign().unwrap(), "CONVERGE");
+        } else {
+            panic!()
+        }
+
+        let (messages, next) = simulator.tick(0.5).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(next, 1.);
+
+        let (messages, _) = simulator.tick(1.).unwrap();
+        assert_eq!(messages.len(), 5);
+    }
+}
@@ -0,0 +1,504 @@
+//! Stratux vendor messages (heartbeat ID 0xCC and status ID 0x53 "SX")
+//!
+//! Reference: <https://github.com/cyoung/stratux/blob/master/notes/app-vendor-integration.md>
+use crate::report::{lat_long_f32_to_u32, lat_long_u32_to_f32};
+use deku::prelude::*;
+use enum_ordinalize::Ordinalize;
+
+/// Stratux heartbeat (message ID 0xCC)
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct StratuxHeartbeat {
+    #[deku(bits = "6")]
+    protocol_version: u8,
+
+    #[deku(bits = "1")]
+    gps_valid: bool,
+
+    #[deku(bits = "1")]
+    ahrs_valid: bool,
+}
+
+impl Default for StratuxHeartbeat {
+    fn default() -> Self {
+        Self {
+            protocol_version: 1,
+            gps_valid: false,
+            ahrs_valid: false,
+        }
+    }
+}
+
+impl StratuxHeartbeat {
+    /// Protocol version (0 - 63)
+    pub fn get_protocol_version(&self) -> u8 {
+        self.protocol_version
+    }
+
+    /// Protocol version (0 - 63)
+    pub fn set_protocol_version(&mut self, value: u8) {
+        self.protocol_version = value & 0x3F;
+    }
+
+    pub fn get_gps_valid(&self) -> bool {
+        self.gps_valid
+    }
+
+    pub fn set_gps_valid(&mut self, value: bool) {
+        self.gps_valid = value;
+    }
+
+    pub fn get_ahrs_valid(&self) -> bool {
+        self.ahrs_valid
+    }
+
+    pub fn set_ahrs_valid(&mut self, value: bool) {
+        self.ahrs_valid = value;
+    }
+}
+
+/// Stratux status (message ID 0x53, sub ID 0x58)
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big", magic = b"X")]
+pub struct StratuxStatus {
+    status_version: u8,
+    firmware_version: [u8; 4],
+    hardware_revision: u32,
+    reserved1: u8,
+
+    #[deku(bits = "7")]
+    reserved2: u8,
+
+    #[deku(bits = "1")]
+    ahrs_enabled: bool,
+
+    #[deku(bits = "1")]
+    gps_enabled: bool,
+
+    #[deku(bits = "1")]
+    es_enabled: bool,
+
+    #[deku(bits = "1")]
+    uat_enabled: bool,
+
+    #[deku(bits = "1")]
+    cpu_temperature_valid: bool,
+
+    #[deku(bits = "1")]
+    pressure_altitude_valid: bool,
+
+    #[deku(bits = "1")]
+    ahrs_valid: bool,
+
+    #[deku(bits = "2")]
+    gps_fix: u8,
+
+    reserved3: u8,
+
+    #[deku(bits = "5")]
+    reserved4: u8,
+
+    #[deku(bits = "1")]
+    imu_connected: bool,
+
+    #[deku(bits = "2")]
+    radios: u8,
+
+    satellites_locked: u8,
+    satellites_tracked: u8,
+    uat_traffic_targets: u16,
+    es_traffic_targets: u16,
+    uat_messages_per_minute: u16,
+    es_messages_per_minute: u16,
+    cpu_temperature: i16,
+
+    #[deku(update = "self.towers.len()")]
+    tower_count: u8,
+
+    #[deku(count = "tower_count")]
+    towers: Vec<StratuxTower>,
+}
+
+impl Default for StratuxStatus {
+    fn default() -> Self {
+        Self {
+            status_version: 1,
+            firmware_version: [0; 4],
+            hardware_revision: 0xFFFFFFFF,
+            reserved1: 0,
+            reserved2: 0,
+            ahrs_enabled: false,
+            gps_enabled: false,
+            es_enabled: false,
+            uat_enabled: false,
+            cpu_temperature_valid: false,
+            pressure_altitude_valid: false,
+            ahrs_valid: false,
+            gps_fix: 0,
+            reserved3: 0,
+            reserved4: 0,
+            imu_connected: false,
+            radios: 0,
+            satellites_locked: 0,
+            satellites_tracked: 0,
+            uat_traffic_targets: 0,
+            es_traffic_targets: 0,
+            uat_messages_per_minute: 0,
+            es_messages_per_minute: 0,
+            cpu_temperature: 0,
+            tower_count: 0,
+            towers: vec![],
+        }
+    }
+}
+
+impl StratuxStatus {
+    /// Status message version
+    pub fn get_status_version(&self) -> u8 {
+        self.status_version
+    }
+
+    /// Firmware version (major, minor, release type, build)
+    pub fn get_firmware_version(&self) -> [u8; 4] {
+        self.firmware_version
+    }
+
+    /// Firmware version (major, minor, release type, build)
+    pub fn set_firmware_version(&mut self, value: [u8; 4]) {
+        self.firmware_version = value;
+    }
+
+    /// Hardware revision, if reported
+    pub fn get_hardware_revision(&self) -> Option<u32> {
+        match self.hardware_revision {
+            0xFFFFFFFF => None,
+            value => Some(value),
+        }
+    }
+
+    /// Hardware revision, if reported
+    pub fn set_hardware_revision(&mut self, value: Option<u32>) {
+        self.hardware_revision = value.unwrap_or(0xFFFFFFFF);
+    }
+
+    pub fn get_ahrs_enabled(&self) -> bool {
+        self.ahrs_enabled
+    }
+
+    pub fn set_ahrs_enabled(&mut self, value: bool) {
+        self.ahrs_enabled = value;
+    }
+
+    pub fn get_gps_enabled(&self) -> bool {
+        self.gps_enabled
+    }
+
+    pub fn set_gps_enabled(&mut self, value: bool) {
+        self.gps_enabled = value;
+    }
+
+    /// 1090ES receiver enabled
+    pub fn get_es_enabled(&self) -> bool {
+        self.es_enabled
+    }
+
+    /// 1090ES receiver enabled
+    pub fn set_es_enabled(&mut self, value: bool) {
+        self.es_enabled = value;
+    }
+
+    /// 978 UAT receiver enabled
+    pub fn get_uat_enabled(&self) -> bool {
+        self.uat_enabled
+    }
+
+    /// 978 UAT receiver enabled
+    pub fn set_uat_enabled(&mut self, value: bool) {
+        self.uat_enabled = value;
+    }
+
+    pub fn get_pressure_altitude_valid(&self) -> bool {
+        self.pressure_altitude_valid
+    }
+
+    pub fn set_pressure_altitude_valid(&mut self, value: bool) {
+        self.pressure_altitude_valid = value;
+    }
+
+    pub fn get_ahrs_valid(&self) -> bool {
+        self.ahrs_valid
+    }
+
+    pub fn set_ahrs_valid(&mut self, value: bool) {
+        self.ahrs_valid = value;
+    }
+
+    pub fn get_gps_fix(&self) -> GpsFix {
+        GpsFix::VARIANTS[self.gps_fix as usize]
+    }
+
+    pub fn set_gps_fix(&mut self, value: GpsFix) {
+        self.gps_fix = value.ordinal() as u8;
+    }
+
+    /// Inertial measurement unit connected
+    pub fn get_imu_connected(&self) -> bool {
+        self.imu_connected
+    }
+
+    /// Inertial measurement unit connected
+    pub fn set_imu_connected(&mut self, value: bool) {
+        self.imu_connected = value;
+    }
+
+    /// Number of connected software defined radios (0 - 3)
+    pub fn get_radios(&self) -> u8 {
+        self.radios
+    }
+
+    /// Number of connected software defined radios (0 - 3)
+    pub fn set_radios(&mut self, value: u8) {
+        self.radios = value.min(3);
+    }
+
+    /// Number of GPS satellites used in the position solution
+    pub fn get_satellites_locked(&self) -> u8 {
+        self.satellites_locked
+    }
+
+    /// Number of GPS satellites used in the position solution
+    pub fn set_satellites_locked(&mut self, value: u8) {
+        self.satellites_locked = value;
+    }
+
+    /// Number of GPS satellites in view
+    pub fn get_satellites_tracked(&self) -> u8 {
+        self.satellites_tracked
+    }
+
+    /// Number of GPS satellites in view
+    pub fn set_satellites_tracked(&mut self, value: u8) {
+        self.satellites_tracked = value;
+    }
+
+    /// Number of traffic targets received on 978 UAT
+    pub fn get_uat_traffic_targets(&self) -> u16 {
+        self.uat_traffic_targets
+    }
+
+    /// Number of traffic targets received on 978 UAT
+    pub fn set_uat_traffic_targets(&mut self, value: u16) {
+        self.uat_traffic_targets = value;
+    }
+
+    /// Number of traffic targets received on 1090ES
+    pub fn get_es_traffic_targets(&self) -> u16 {
+        self.es_traffic_targets
+    }
+
+    /// Number of traffic targets received on 1090ES
+    pub fn set_es_traffic_targets(&mut self, value: u16) {
+        self.es_traffic_targets = value;
+    }
+
+    /// Number of 978 UAT messages received in the last minute
+    pub fn get_uat_messages_per_minute(&self) -> u16 {
+        self.uat_messages_per_minute
+    }
+
+    /// Number of 978 UAT messages received in the last minute
+    pub fn set_uat_messages_per_minute(&mut self, value: u16) {
+        self.uat_messages_per_minute = value;
+    }
+
+    /// Number of 1090ES messages received in the last minute
+    pub fn get_es_messages_per_minute(&self) -> u16 {
+        self.es_messages_per_minute
+    }
+
+    /// Number of 1090ES messages received in the last minute
+    pub fn set_es_messages_per_minute(&mut self, value: u16) {
+        self.es_messages_per_minute = value;
+    }
+
+    /// CPU temperature (in degrees Celsius), if valid
+    pub fn get_cpu_temperature(&self) -> Option<f32> {
+        if self.cpu_temperature_valid {
+            Some(self.cpu_temperature as f32 / 10.)
+        } else {
+            None
+        }
+    }
+
+    /// CPU temperature (in degrees Celsius), if valid
+    pub fn set_cpu_temperature(&mut self, value: Option<f32>) {
+        self.cpu_temperature_valid = value.is_some();
+        self.cpu_temperature = value.map_or(0, |value| (value * 10.).round() as i16);
+    }
+
+    /// UAT ground stations being received
+    pub fn get_towers(&self) -> &[StratuxTower] {
+        &self.towers
+    }
+
+    /// UAT ground stations being received (at most 255)
+    pub fn set_towers(&mut self, value: Vec<StratuxTower>) {
+        self.tower_count = value.len() as u8;
+        self.towers = value;
+    }
+}
+
+/// UAT ground station position
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq, Clone, Copy)]
+#[deku(
+    bit_order = "order",
+    endian = "endian",
+    ctx = "endian: deku::ctx::Endian, order: deku::ctx::Order"
+)]
+pub struct StratuxTower {
+    #[deku(bits = "24")]
+    latitude: u32,
+
+    #[deku(bits = "24")]
+    longitude: u32,
+}
+
+impl StratuxTower {
+    pub fn get_latitude(&self) -> f32 {
+        lat_long_u32_to_f32(self.latitude)
+    }
+
+    pub fn set_latitude(&mut self, value: f32) {
+        self.latitude = lat_long_f32_to_u32(value);
+    }
+
+    pub fn get_longitude(&self) -> f32 {
+        lat_long_u32_to_f32(self.longitude)
+    }
+
+    pub fn set_longitude(&mut self, value: f32) {
+        self.longitude = lat_long_f32_to_u32(value);
+    }
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GpsFix {
+    NoFix,
+    Fix3D,
+    Differential,
+    Reserved,
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::{GpsFix, StratuxHeartbeat, StratuxStatus, StratuxTower};
+    use crate::{
+        message::{create_message, read_message},
+        payload::Payload,
+    };
+
+    #[test]
+    fn test_heartbeat() {
+        let data = [0x07];
+        let heartbeat = StratuxHeartbeat::try_from(&data[..]).unwrap();
+
+        assert_eq!(heartbeat.get_protocol_version(), 1);
+        assert!(heartbeat.get_gps_valid());
+        assert!(heartbeat.get_ahrs_valid());
+
+        let mut new_heartbeat = StratuxHeartbeat::default();
+        new_heartbeat.set_gps_valid(true);
+        new_heartbeat.set_ahrs_valid(true);
+
+        assert_eq!(heartbeat, new_heartbeat);
+        assert_eq!(&new_heartbeat.to_bytes().unwrap()[..], &data[..]);
+    }
+
+    #[test]
+    fn test_status() {
+        let data = [
+            0x58, 0x01, 0x01, 0x05, 0x03, 0x02, // Version 1.5 build 2
+            0xFF, 0xFF, 0xFF, 0xFF, // No hardware revision
+            0x00, 0x01, 0xF5, 0x00, // Flags
+            0x06, 0x09, 0x0C, // Hardware, satellites
+            0x00, 0x0A, 0x01, 0x02, 0x03, 0xE8, 0x12, 0x34, // Traffic and messages
+            0x01, 0xC7, // 45.5 degrees
+            0x01, 0x24, 0x8E, 0x49, 0xFF, 0xB1, 0x6E, // One tower
+        ];
+
+        let status = StratuxStatus::try_from(&data[..]).unwrap();
+
+        assert_eq!(status.get_status_version(), 1);
+        assert_eq!(status.get_firmware_version(), [1, 5, 3, 2]);
+        assert_eq!(status.get_hardware_revision(), None);
+        assert!(status.get_ahrs_enabled());
+        assert!(status.get_gps_enabled());
+        assert!(status.get_es_enabled());
+        assert!(status.get_uat_enabled());
+        assert!(!status.get_pressure_altitude_valid());
+        assert!(status.get_ahrs_valid());
+        assert_eq!(status.get_gps_fix(), GpsFix::Fix3D);
+        assert!(status.get_imu_connected());
+        assert_eq!(status.get_radios(), 2);
+        assert_eq!(status.get_satellites_locked(), 9);
+        assert_eq!(status.get_satellites_tracked(), 12);
+        assert_eq!(status.get_uat_traffic_targets(), 10);
+        assert_eq!(status.get_es_traffic_targets(), 258);
+        assert_eq!(status.get_uat_messages_per_minute(), 1000);
+        assert_eq!(status.get_es_messages_per_minute(), 0x1234);
+        assert_eq!(status.get_cpu_temperature(), Some(45.5));
+        assert_eq!(status.get_towers().len(), 1);
+        assert!((status.get_towers()[0].get_latitude() - 51.4066).abs() < 0.0001);
+        assert!((status.get_towers()[0].get_longitude() + 0.4316).abs() < 0.0001);
+
+        let mut tower = StratuxTower::default();
+        tower.set_latitude(status.get_towers()[0].get_latitude());
+        tower.set_longitude(status.get_towers()[0].get_longitude());
+
+        let mut new_status = StratuxStatus::default();
+        new_status.set_firmware_version([1, 5, 3, 2]);
+        new_status.set_ahrs_enabled(true);
+        new_status.set_gps_enabled(true);
+        new_status.set_es_enabled(true);
+        new_status.set_uat_enabled(true);
+        new_status.set_ahrs_valid(true);
+        new_status.set_gps_fix(GpsFix::Fix3D);
+        new_status.set_imu_connected(true);
+        new_status.set_radios(2);
+        new_status.set_satellites_locked(9);
+        new_status.set_satellites_tracked(12);
+        new_status.set_uat_traffic_targets(10);
+        new_status.set_es_traffic_targets(258);
+        new_status.set_uat_messages_per_minute(1000);
+        new_status.set_es_messages_per_minute(0x1234);
+        new_status.set_cpu_temperature(Some(45.5));
+        new_status.set_towers(vec![tower]);
+
+        assert_eq!(status, new_status);
+        assert_eq!(&new_status.to_bytes().unwrap()[..], &data[..]);
+    }
+
+    #[test]
+    fn test_status_message() {
+        let message = create_message(&Payload::StratuxStatus(StratuxStatus::default())).unwrap();
+        let encoded = message.encode();
+
+        assert_eq!(&encoded[1..3], &[0x53, 0x58]);
+
+        let decoded = read_message(&encoded).unwrap();
+        assert!(matches!(
+            decoded.get_payload(),
+            Ok(Payload::StratuxStatus(status)) if status == StratuxStatus::default()
+        ));
+
+        // Other "S" sub messages are not decoded
+        let message = create_message(&Payload::Unknown(0x53, vec![0x01, 0x02])).unwrap();
+        assert!(matches!(
+            message.get_payload(),
+            Ok(Payload::Unknown(0x53, _))
+        ));
+    }
+}
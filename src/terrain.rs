@@ -0,0 +1,84 @@
+//! Height above terrain message
+use deku::prelude::*;
+
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct HeightAboveTerrain {
+    height_above_terrain: i16,
+}
+
+impl HeightAboveTerrain {
+    /// Height Above Terrain (in feet)
+    pub fn get_height_above_terrain(&self) -> Option<i16> {
+        if self.height_above_terrain == i16::MIN {
+            None
+        } else {
+            Some(self.height_above_terrain)
+        }
+    }
+
+    /// Height Above Terrain (in feet)
+    pub fn set_height_above_terrain(&mut self, value: Option<i16>) {
+        if let Some(value) = value {
+            self.height_above_terrain = value.max(-i16::MAX);
+        } else {
+            self.height_above_terrain = i16::MIN; // 0x8000
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::HeightAboveTerrain;
+    use crate::{
+        message::{create_message, read_message},
+        payload::Payload,
+    };
+
+    #[test]
+    fn test_encode_decode() {
+        let data = [0xFF, 0x9C];
+        let hat = HeightAboveTerrain::try_from(&data[..]).unwrap();
+
+        assert_eq!(hat.get_height_above_terrain(), Some(-100));
+
+        let mut new_hat = HeightAboveTerrain::default();
+        new_hat.set_height_above_terrain(Some(-100));
+
+        let encode = new_hat.to_bytes().unwrap();
+
+        assert_eq!(hat, new_hat);
+        assert_eq!(&data[..], &encode[..]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let data = [0x80, 0x00];
+        let mut hat = HeightAboveTerrain::try_from(&data[..]).unwrap();
+
+        assert_eq!(hat.get_height_above_terrain(), None);
+
+        hat.set_height_above_terrain(Some(1234));
+        hat.set_height_above_terrain(None);
+        assert_eq!(&data[..], &hat.to_bytes().unwrap()[..]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut hat = HeightAboveTerrain::default();
+        hat.set_height_above_terrain(Some(2500));
+
+        let encoded = create_message(&Payload::HeightAboveTerrain(hat))
+            .unwrap()
+            .encode();
+        let message = read_message(&encoded[..]).unwrap();
+
+        if let Ok(Payload::HeightAboveTerrain(payload)) = message.get_payload() {
+            assert_eq!(payload.get_height_above_terrain(), Some(2500));
+        } else {
+            panic!("Unexpected payload type");
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Table of traffic targets built from traffic reports
+//!
+//! ```rust
+//! use std::time::{Duration, Instant};
+//! use gdl90codec::payload::Payload;
+//! use gdl90codec::report::Report;
+//! use gdl90codec::traffic::{TrafficTable, TrafficUpdate};
+//!
+//! let mut table = TrafficTable::new();
+//! let now = Instant::now();
+//!
+//! let mut report = Report::default();
+//! report.participant_address = 0xABCDEF;
+//!
+//! let update = table.ingest_at(&Payload::TrafficReport(report), now);
+//! assert_eq!(update, TrafficUpdate::Added);
+//! assert_eq!(table.len(), 1);
+//!
+//! let expired = table.expire_at(now + Duration::from_secs(60));
+//! assert_eq!(expired.len(), 1);
+//! assert!(table.is_empty());
+//! ```
+use crate::{
+    payload::Payload,
+    report::{AddressType, Report},
+};
+use enum_ordinalize::Ordinalize;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Default time after which a target that has not been reported is removed
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Address type and participant address identifying a target
+///
+/// ADS-B and TIS-B reports with ICAO addresses share the same key, so that an aircraft
+/// received both directly and through TIS-B or ADS-R is a single target.
+type Key = (u8, u32);
+
+fn get_key(address_type: AddressType, participant_address: u32) -> Key {
+    let address_type = match address_type {
+        AddressType::TISBWithICAOAddress => AddressType::ADSBWithICAOAddress,
+        address_type => address_type,
+    };
+
+    (address_type.ordinal() as u8, participant_address)
+}
+
+fn get_report_key(report: &Report) -> Key {
+    get_key(report.get_address_type(), report.participant_address)
+}
+
+fn is_tisb(report: &Report) -> bool {
+    matches!(
+        report.get_address_type(),
+        AddressType::TISBWithICAOAddress | AddressType::TISBWithTrackFileID
+    )
+}
+
+/// Outcome of ingesting a payload
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TrafficUpdate {
+    /// First report for a target
+    Added,
+    /// Report for a known target
+    Updated,
+    /// Not a traffic report, a report of the ownship, or a TIS-B report for a target
+    /// with a current ADS-B report
+    Ignored,
+}
+
+/// Traffic target and the time it was reported
+#[derive(Debug, Clone)]
+pub struct Target {
+    report: Report,
+    first_seen: Instant,
+    last_seen: Instant,
+    update_count: u32,
+}
+
+impl Target {
+    /// Latest report for the target
+    pub fn get_report(&self) -> &Report {
+        &self.report
+    }
+
+    pub fn get_first_seen(&self) -> Instant {
+        self.first_seen
+    }
+
+    pub fn get_last_seen(&self) -> Instant {
+        self.last_seen
+    }
+
+    /// Number of reports since the target was added, including the first
+    pub fn get_update_count(&self) -> u32 {
+        self.update_count
+    }
+
+    /// Time since the target was last reported
+    pub fn get_age(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.last_seen)
+    }
+}
+
+/// Traffic targets keyed by participant address
+///
+/// Targets are added and updated from [Payload::TrafficReport], and removed by
+/// [TrafficTable::expire] once they have not been reported for the timeout. Traffic
+/// with the ownship address, from [Payload::OwnershipReport], is suppressed.
+#[derive(Debug)]
+pub struct TrafficTable {
+    targets: HashMap<Key, Target>,
+    ownship: Option<Key>,
+    timeout: Duration,
+}
+
+impl Default for TrafficTable {
+    fn default() -> Self {
+        Self {
+            targets: HashMap::new(),
+            ownship: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl TrafficTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Address of the latest ownship report, if any
+    pub fn get_ownship_address(&self) -> Option<u32> {
+        self.ownship.map(|(_, address)| address)
+    }
+
+    /// Ingest a payload received now
+    pub fn ingest(&mut self, payload: &Payload) -> TrafficUpdate {
+        self.ingest_at(payload, Instant::now())
+    }
+
+    /// Ingest a payload received at `now`
+    pub fn ingest_at(&mut self, payload: &Payload, now: Instant) -> TrafficUpdate {
+        match payload {
+            Payload::OwnershipReport(report) => {
+                let key = get_report_key(report);
+                self.ownship = Some(key);
+                self.targets.remove(&key);
+                TrafficUpdate::Ignored
+            }
+            Payload::TrafficReport(report) => self.update(report, now),
+            _ => TrafficUpdate::Ignored,
+        }
+    }
+
+    fn update(&mut self, report: &Report, now: Instant) -> TrafficUpdate {
+        let key = get_report_key(report);
+
+        if self.ownship == Some(key) {
+            return TrafficUpdate::Ignored;
+        }
+
+        let Some(target) = self.targets.get_mut(&key) else {
+            self.targets.insert(
+                key,
+                Target {
+                    report: report.clone(),
+                    first_seen: now,
+                    last_seen: now,
+                    update_count: 1,
+                },
+            );
+            return TrafficUpdate::Added;
+        };
+
+        // Direct ADS-B reports take precedence over TIS-B and ADS-R until they time out
+        if is_tisb(report) && !is_tisb(&target.report) && target.get_age(now) < self.timeout {
+            return TrafficUpdate::Ignored;
+        }
+
+        target.report = report.clone();
+        target.last_seen = now;
+        target.update_count = target.update_count.saturating_add(1);
+        TrafficUpdate::Updated
+    }
+
+    /// Remove the targets not reported within the timeout, as of now
+    pub fn expire(&mut self) -> Vec<Target> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Remove the targets not reported within the timeout, as of `now`
+    pub fn expire_at(&mut self, now: Instant) -> Vec<Target> {
+        let timeout = self.timeout;
+        let expired = self
+            .targets
+            .iter()
+            .filter(|(_, target)| target.get_age(now) >= timeout)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        expired
+            .iter()
+            .filter_map(|key| self.targets.remove(key))
+            .collect()
+    }
+
+    /// Target for a participant address, TIS-B ICAO addresses being the same as ADS-B
+    pub fn get(&self, address_type: AddressType, participant_address: u32) -> Option<&Target> {
+        self.targets
+            .get(&get_key(address_type, participant_address))
+    }
+
+    /// Targets in no particular order
+    pub fn targets(&self) -> impl Iterator<Item = &Target> {
+        self.targets.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.targets.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TrafficTable, TrafficUpdate};
+    use crate::{
+        payload::Payload,
+        report::{AddressType, Report},
+    };
+    use std::time::{Duration, Instant};
+
+    fn report(address_type: AddressType, address: u32, altitude: i32) -> Report {
+        let mut report = Report::default();
+        report.set_address_type(address_type);
+        report.participant_address = address;
+        report.set_altitude(altitude);
+        report
+    }
+
+    fn traffic(address_type: AddressType, address: u32, altitude: i32) -> Payload {
+        Payload::TrafficReport(report(address_type, address, altitude))
+    }
+
+    #[test]
+    fn test_aging() {
+        let mut table = TrafficTable::new();
+        table.set_timeout(Duration::from_secs(10));
+        let start = Instant::now();
+        let later = |seconds| start + Duration::from_secs(seconds);
+
+        let adsb = AddressType::ADSBWithICAOAddress;
+        let update = table.ingest_at(&traffic(adsb, 0x01, 1000), start);
+        assert_eq!(update, TrafficUpdate::Added);
+        let update = table.ingest_at(&traffic(adsb, 0x02, 2000), later(5));
+        assert_eq!(update, TrafficUpdate::Added);
+        let update = table.ingest_at(&traffic(adsb, 0x01, 1100), later(8));
+        assert_eq!(update, TrafficUpdate::Updated);
+
+        let target = table.get(adsb, 0x01).unwrap();
+        assert_eq!(target.get_report().get_altitude(), 1100);
+        assert_eq!(target.get_first_seen(), start);
+        assert_eq!(target.get_last_seen(), later(8));
+        assert_eq!(target.get_update_count(), 2);
+
+        assert!(table.expire_at(later(14)).is_empty());
+
+        let expired = table.expire_at(later(15));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].get_report().participant_address, 0x02);
+        assert_eq!(table.len(), 1);
+
+        table.expire_at(later(18));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_address_types() {
+        let mut table = TrafficTable::new();
+        let start = Instant::now();
+        let later = |seconds| start + Duration::from_secs(seconds);
+
+        let adsb = AddressType::ADSBWithICAOAddress;
+        let tisb = AddressType::TISBWithICAOAddress;
+        let track_file = AddressType::TISBWithTrackFileID;
+
+        // Same ICAO address through ADS-B and TIS-B is one target, preferring ADS-B
+        table.ingest_at(&traffic(adsb, 0x01, 1000), start);
+        let update = table.ingest_at(&traffic(tisb, 0x01, 1200), later(1));
+        assert_eq!(update, TrafficUpdate::Ignored);
+        assert_eq!(table.len(), 1);
+
+        let update = table.ingest_at(&traffic(tisb, 0x01, 1200), later(31));
+        assert_eq!(update, TrafficUpdate::Updated);
+        let target = table.get(adsb, 0x01).unwrap();
+        assert_eq!(target.get_report().get_address_type(), tisb);
+
+        let update = table.ingest_at(&traffic(adsb, 0x01, 1300), later(32));
+        assert_eq!(update, TrafficUpdate::Updated);
+        let target = table.get(tisb, 0x01).unwrap();
+        assert_eq!(target.get_report().get_altitude(), 1300);
+
+        // Track file IDs are not ICAO addresses
+        let update = table.ingest_at(&traffic(track_file, 0x01, 5000), later(32));
+        assert_eq!(update, TrafficUpdate::Added);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn test_ownship() {
+        let mut table = TrafficTable::new();
+        let adsb = AddressType::ADSBWithICAOAddress;
+
+        table.ingest(&traffic(adsb, 0xABCDEF, 3000));
+        table.ingest(&traffic(adsb, 0x123456, 3000));
+        assert_eq!(table.len(), 2);
+
+        let ownship = Payload::OwnershipReport(report(adsb, 0xABCDEF, 3000));
+        assert_eq!(table.ingest(&ownship), TrafficUpdate::Ignored);
+        assert_eq!(table.get_ownship_address(), Some(0xABCDEF));
+        assert_eq!(table.len(), 1);
+
+        // Ownship relayed through TIS-B/ADS-R
+        let update = table.ingest(&traffic(AddressType::TISBWithICAOAddress, 0xABCDEF, 3000));
+        assert_eq!(update, TrafficUpdate::Ignored);
+        assert!(table.get(adsb, 0xABCDEF).is_none());
+        assert!(table.get(adsb, 0x123456).is_some());
+    }
+}
@@ -0,0 +1,863 @@
+//! UAT ADS-B payload elements carried by pass-through reports
+//!
+//! A payload is made up of a header (HDR) followed by, depending on the payload
+//! type code, a state vector (SV), mode status (MS) and auxiliary state vector
+//! (AUX SV).
+//!
+//! Reference: DO-282B
+use crate::{
+    error::GDL90Error,
+    passthrough::{BASIC_UAT_PAYLOAD_LENGTH, LONG_UAT_PAYLOAD_LENGTH},
+    report::{
+        lat_long_u32_to_f32, lat_u23_to_f32, AddressType, EmergencyPriorityCode, EmitterCategory,
+        FlightStage, HeadingType, NACp, Report, ReportType, TrafficAlertStatus, NIC,
+    },
+};
+use deku::prelude::*;
+use enum_ordinalize::Ordinalize;
+
+const HEADER_RANGE: std::ops::Range<usize> = 0..4;
+const STATE_VECTOR_RANGE: std::ops::Range<usize> = 4..17;
+const MODE_STATUS_RANGE: std::ops::Range<usize> = 17..29;
+const AUXILIARY_STATE_VECTOR_RANGE: std::ops::Range<usize> = 29..34;
+
+const BASE40_ALPHABET: &[u8; 40] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ  ..";
+
+/// Decoded UAT ADS-B payload
+#[derive(Debug, PartialEq)]
+pub struct UatAdsbPayload {
+    header: UatHeader,
+    state_vector: Option<StateVector>,
+    mode_status: Option<ModeStatus>,
+    auxiliary_state_vector: Option<AuxiliaryStateVector>,
+}
+
+impl UatAdsbPayload {
+    pub fn get_header(&self) -> &UatHeader {
+        &self.header
+    }
+
+    pub fn get_state_vector(&self) -> Option<&StateVector> {
+        self.state_vector.as_ref()
+    }
+
+    pub fn get_mode_status(&self) -> Option<&ModeStatus> {
+        self.mode_status.as_ref()
+    }
+
+    pub fn get_auxiliary_state_vector(&self) -> Option<&AuxiliaryStateVector> {
+        self.auxiliary_state_vector.as_ref()
+    }
+
+    /// Type of the secondary altitude, the opposite of the state vector altitude type
+    pub fn get_secondary_altitude_type(&self) -> Option<AltitudeType> {
+        match (&self.state_vector, &self.auxiliary_state_vector) {
+            (Some(state_vector), Some(_)) => Some(match state_vector.get_altitude_type() {
+                AltitudeType::Barometric => AltitudeType::Geometric,
+                AltitudeType::Geometric => AltitudeType::Barometric,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for UatAdsbPayload {
+    type Error = GDL90Error;
+
+    fn try_from(input: &[u8]) -> Result<Self, Self::Error> {
+        if input.len() != BASIC_UAT_PAYLOAD_LENGTH && input.len() != LONG_UAT_PAYLOAD_LENGTH {
+            return Err(GDL90Error::Parse(
+                format!("Unexpected UAT payload length {}", input.len()).into(),
+            ));
+        }
+
+        let header = UatHeader::try_from(&input[HEADER_RANGE])?;
+        let is_long = input.len() == LONG_UAT_PAYLOAD_LENGTH;
+
+        let (has_state_vector, has_mode_status, has_auxiliary_state_vector) =
+            match header.payload_type_code {
+                0 => (true, false, false),
+                _ if !is_long => (false, false, false),
+                1 => (true, true, true),
+                2 | 5 | 6 => (true, false, true),
+                3 => (true, true, false),
+                4 | 7..=10 => (true, false, false),
+                _ => (false, false, false),
+            };
+
+        let state_vector = if has_state_vector {
+            Some(StateVector::try_from(&input[STATE_VECTOR_RANGE])?)
+        } else {
+            None
+        };
+
+        let mode_status = if has_mode_status {
+            Some(ModeStatus::try_from(&input[MODE_STATUS_RANGE])?)
+        } else {
+            None
+        };
+
+        let auxiliary_state_vector = if has_auxiliary_state_vector {
+            Some(AuxiliaryStateVector::try_from(
+                &input[AUXILIARY_STATE_VECTOR_RANGE],
+            )?)
+        } else {
+            None
+        };
+
+        Ok(UatAdsbPayload {
+            header,
+            state_vector,
+            mode_status,
+            auxiliary_state_vector,
+        })
+    }
+}
+
+impl TryFrom<&UatAdsbPayload> for Report {
+    type Error = GDL90Error;
+
+    /// Convert a payload with a state vector into a traffic [Report]
+    fn try_from(payload: &UatAdsbPayload) -> Result<Self, Self::Error> {
+        let Some(state_vector) = payload.get_state_vector() else {
+            return Err(GDL90Error::InvalidParam(
+                "UAT payload has no state vector".into(),
+            ));
+        };
+
+        let mut report = Report::default();
+        report.participant_address = payload.header.address;
+
+        report.set_traffic_alert_status(TrafficAlertStatus::NoAlert);
+        report.set_address_type(payload.header.get_address_qualifier().into());
+        report.set_report_type(ReportType::ReportIsUpdated);
+
+        if let (Some(latitude), Some(longitude)) =
+            (state_vector.get_latitude(), state_vector.get_longitude())
+        {
+            report.set_latitude(latitude);
+            report.set_longitude(longitude);
+        }
+
+        let pressure_altitude = match state_vector.get_altitude_type() {
+            AltitudeType::Barometric => state_vector.get_altitude(),
+            AltitudeType::Geometric => payload
+                .get_auxiliary_state_vector()
+                .and_then(|aux| aux.get_secondary_altitude()),
+        };
+        if let Some(altitude) = pressure_altitude {
+            report.set_altitude(altitude);
+        } else {
+            report.set_altitude_invalid();
+        }
+
+        report.set_flight_stage(if state_vector.is_airborne() {
+            FlightStage::Airborne
+        } else {
+            FlightStage::OnGround
+        });
+        report.set_navigation_integrity_category(state_vector.get_navigation_integrity_category());
+        report.set_horizontal_velocity(state_vector.get_horizontal_velocity());
+        report.set_vertical_velocity(state_vector.get_vertical_rate().map(|rate| rate / 64));
+
+        if let Some((track, heading_type)) = state_vector.get_track() {
+            report.set_heading_type(heading_type);
+            report.set_track_heading(track);
+        } else {
+            report.set_heading_type(HeadingType::NotValid);
+        }
+
+        if let Some(mode_status) = payload.get_mode_status() {
+            report.set_navigation_accuracy_category_for_position(
+                mode_status.get_navigation_accuracy_category_for_position(),
+            );
+            report.set_emitter_category(mode_status.get_emitter_category());
+            report.set_emergency_priority_code(mode_status.get_emergency_priority_code());
+
+            let mut callsign = [b' '; 8];
+            for (dst, src) in callsign.iter_mut().zip(mode_status.get_callsign().bytes()) {
+                *dst = src;
+            }
+            report.set_callsign(callsign);
+        } else {
+            report.set_callsign([b' '; 8]);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Payload header (HDR)
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct UatHeader {
+    #[deku(bits = "5")]
+    payload_type_code: u8,
+
+    #[deku(bits = "3")]
+    address_qualifier: u8,
+
+    #[deku(bits = "24")]
+    address: u32,
+}
+
+impl UatHeader {
+    pub fn get_payload_type_code(&self) -> u8 {
+        self.payload_type_code
+    }
+
+    pub fn get_address_qualifier(&self) -> AddressQualifier {
+        AddressQualifier::VARIANTS[self.address_qualifier as usize]
+    }
+
+    /// 24 bit participant address
+    pub fn get_address(&self) -> u32 {
+        self.address
+    }
+}
+
+/// State vector (SV)
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct StateVector {
+    #[deku(bits = "23")]
+    latitude: u32,
+
+    #[deku(bits = "24")]
+    longitude: u32,
+
+    #[deku(bits = "1")]
+    altitude_type: u8,
+
+    #[deku(bits = "12")]
+    altitude: u16,
+
+    #[deku(bits = "4")]
+    navigation_integrity_category: u8,
+
+    #[deku(bits = "2")]
+    air_ground_state: u8,
+
+    #[deku(bits = "1")]
+    reserved: u8,
+
+    #[deku(bits = "11")]
+    north_velocity_or_ground_speed: u16,
+
+    #[deku(bits = "11")]
+    east_velocity_or_track: u16,
+
+    #[deku(bits = "11")]
+    vertical_velocity: u16,
+
+    #[deku(bits = "4")]
+    utc_coupled_or_tisb_site_id: u8,
+}
+
+impl StateVector {
+    fn has_position(&self) -> bool {
+        self.navigation_integrity_category != 0 || self.latitude != 0 || self.longitude != 0
+    }
+
+    fn is_airborne(&self) -> bool {
+        matches!(
+            self.get_air_ground_state(),
+            AirGroundState::AirborneSubsonic | AirGroundState::AirborneSupersonic
+        )
+    }
+
+    /// Latitude (in degrees)
+    pub fn get_latitude(&self) -> Option<f32> {
+        self.has_position().then(|| lat_u23_to_f32(self.latitude))
+    }
+
+    /// Longitude (in degrees)
+    pub fn get_longitude(&self) -> Option<f32> {
+        self.has_position()
+            .then(|| lat_long_u32_to_f32(self.longitude))
+    }
+
+    pub fn get_altitude_type(&self) -> AltitudeType {
+        AltitudeType::VARIANTS[self.altitude_type as usize]
+    }
+
+    /// Altitude (in feet)
+    pub fn get_altitude(&self) -> Option<i32> {
+        decode_altitude(self.altitude)
+    }
+
+    pub fn get_navigation_integrity_category(&self) -> NIC {
+        NIC::VARIANTS[self.navigation_integrity_category as usize]
+    }
+
+    pub fn get_air_ground_state(&self) -> AirGroundState {
+        AirGroundState::VARIANTS[self.air_ground_state as usize]
+    }
+
+    /// North/South velocity, north positive (in knots), when airborne
+    pub fn get_north_velocity(&self) -> Option<i16> {
+        self.is_airborne()
+            .then(|| self.decode_velocity(self.north_velocity_or_ground_speed))
+            .flatten()
+    }
+
+    /// East/West velocity, east positive (in knots), when airborne
+    pub fn get_east_velocity(&self) -> Option<i16> {
+        self.is_airborne()
+            .then(|| self.decode_velocity(self.east_velocity_or_track))
+            .flatten()
+    }
+
+    /// Horizontal velocity (in knots)
+    pub fn get_horizontal_velocity(&self) -> Option<u16> {
+        match self.get_air_ground_state() {
+            AirGroundState::OnGround => match self.north_velocity_or_ground_speed & 0x3FF {
+                0 => None,
+                value => Some(value - 1),
+            },
+            AirGroundState::Reserved => None,
+            _ => {
+                let north = self.get_north_velocity()? as f32;
+                let east = self.get_east_velocity()? as f32;
+                Some(north.hypot(east) as u16)
+            }
+        }
+    }
+
+    /// Track or heading (in degrees), and which of the two it is
+    pub fn get_track(&self) -> Option<(f32, HeadingType)> {
+        match self.get_air_ground_state() {
+            AirGroundState::OnGround => {
+                let heading_type = match (self.east_velocity_or_track & 0x600) >> 9 {
+                    1 => HeadingType::TrueTrackAngle,
+                    2 => HeadingType::HeadingMagnetic,
+                    3 => HeadingType::HeadingTrue,
+                    _ => return None,
+                };
+                let angle = (self.east_velocity_or_track & 0x1FF) as f32 * 360. / 512.;
+                Some((angle, heading_type))
+            }
+            AirGroundState::Reserved => None,
+            _ => {
+                let north = self.get_north_velocity()? as f32;
+                let east = self.get_east_velocity()? as f32;
+                if north == 0. && east == 0. {
+                    return None;
+                }
+                let track = east.atan2(north).to_degrees().rem_euclid(360.);
+                Some((track, HeadingType::TrueTrackAngle))
+            }
+        }
+    }
+
+    /// Vertical rate (in feet per minute), when airborne
+    pub fn get_vertical_rate(&self) -> Option<i16> {
+        if !self.is_airborne() {
+            return None;
+        }
+
+        match self.vertical_velocity & 0x1FF {
+            0 => None,
+            value => {
+                let rate = (value as i16 - 1) * 64;
+                if self.vertical_velocity & 0x200 != 0 {
+                    Some(-rate)
+                } else {
+                    Some(rate)
+                }
+            }
+        }
+    }
+
+    /// Source of the vertical rate, when airborne
+    pub fn get_vertical_rate_source(&self) -> Option<AltitudeType> {
+        if self.is_airborne() && self.vertical_velocity & 0x1FF != 0 {
+            Some(if self.vertical_velocity & 0x400 != 0 {
+                AltitudeType::Barometric
+            } else {
+                AltitudeType::Geometric
+            })
+        } else {
+            None
+        }
+    }
+
+    /// UTC coupled (ADS-B reports only)
+    pub fn get_utc_coupled(&self) -> bool {
+        self.utc_coupled_or_tisb_site_id & 0x08 != 0
+    }
+
+    /// TIS-B site ID (TIS-B and ADS-R reports only)
+    pub fn get_tisb_site_id(&self) -> u8 {
+        self.utc_coupled_or_tisb_site_id
+    }
+
+    fn decode_velocity(&self, value: u16) -> Option<i16> {
+        let magnitude = match value & 0x3FF {
+            0 => return None,
+            magnitude => magnitude as i16 - 1,
+        };
+
+        let magnitude = if self.get_air_ground_state() == AirGroundState::AirborneSupersonic {
+            magnitude * 4
+        } else {
+            magnitude
+        };
+
+        if value & 0x400 != 0 {
+            Some(-magnitude)
+        } else {
+            Some(magnitude)
+        }
+    }
+}
+
+/// Mode status (MS)
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct ModeStatus {
+    emitter_category_and_callsign: [u16; 3],
+
+    #[deku(bits = "3")]
+    emergency_priority_code: u8,
+
+    #[deku(bits = "3")]
+    uat_version: u8,
+
+    #[deku(bits = "2")]
+    source_integrity_level: u8,
+
+    #[deku(bits = "6")]
+    transmit_mso: u8,
+
+    #[deku(bits = "2")]
+    reserved1: u8,
+
+    #[deku(bits = "4")]
+    navigation_accuracy_category_for_position: u8,
+
+    #[deku(bits = "3")]
+    navigation_accuracy_category_for_velocity: u8,
+
+    #[deku(bits = "1")]
+    nic_baro: bool,
+
+    #[deku(bits = "1")]
+    cdti: bool,
+
+    #[deku(bits = "1")]
+    acas: bool,
+
+    #[deku(bits = "1")]
+    acas_ra_active: bool,
+
+    #[deku(bits = "1")]
+    ident_active: bool,
+
+    #[deku(bits = "1")]
+    atc_services: bool,
+
+    #[deku(bits = "1")]
+    heading_type: u8,
+
+    #[deku(bits = "1")]
+    callsign_type: u8,
+
+    #[deku(bits = "1")]
+    reserved2: u8,
+
+    reserved3: u16,
+}
+
+impl ModeStatus {
+    pub fn get_emitter_category(&self) -> EmitterCategory {
+        let value = self.emitter_category_and_callsign[0] / 1600;
+        EmitterCategory::VARIANTS[(value % 40) as usize]
+    }
+
+    /// Call sign (base 40 encoded), with trailing spaces removed
+    pub fn get_callsign(&self) -> String {
+        let [first, second, third] = self.emitter_category_and_callsign;
+
+        let characters = [
+            first / 40,
+            first,
+            second / 1600,
+            second / 40,
+            second,
+            third / 1600,
+            third / 40,
+            third,
+        ];
+
+        characters
+            .iter()
+            .map(|c| BASE40_ALPHABET[(c % 40) as usize] as char)
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+
+    /// Whether the call sign field holds a flight ID or a squawk code
+    pub fn get_callsign_type(&self) -> CallsignType {
+        CallsignType::VARIANTS[self.callsign_type as usize]
+    }
+
+    pub fn get_emergency_priority_code(&self) -> EmergencyPriorityCode {
+        EmergencyPriorityCode::VARIANTS[self.emergency_priority_code as usize]
+    }
+
+    pub fn get_uat_version(&self) -> u8 {
+        self.uat_version
+    }
+
+    /// Source integrity level (SIL)
+    pub fn get_source_integrity_level(&self) -> u8 {
+        self.source_integrity_level
+    }
+
+    pub fn get_transmit_mso(&self) -> u8 {
+        self.transmit_mso
+    }
+
+    pub fn get_navigation_accuracy_category_for_position(&self) -> NACp {
+        NACp::VARIANTS[self.navigation_accuracy_category_for_position as usize]
+    }
+
+    /// Navigation accuracy category for velocity (NACv)
+    pub fn get_navigation_accuracy_category_for_velocity(&self) -> u8 {
+        self.navigation_accuracy_category_for_velocity
+    }
+
+    pub fn get_nic_baro(&self) -> bool {
+        self.nic_baro
+    }
+
+    /// Capability codes (CDTI)
+    pub fn get_cdti(&self) -> bool {
+        self.cdti
+    }
+
+    /// Capability codes (ACAS)
+    pub fn get_acas(&self) -> bool {
+        self.acas
+    }
+
+    /// Operational modes (ACAS RA active)
+    pub fn get_acas_ra_active(&self) -> bool {
+        self.acas_ra_active
+    }
+
+    /// Operational modes (IDENT switch active)
+    pub fn get_ident_active(&self) -> bool {
+        self.ident_active
+    }
+
+    /// Operational modes (receiving ATC services)
+    pub fn get_atc_services(&self) -> bool {
+        self.atc_services
+    }
+
+    /// Heading reference for the state vector track when on the ground
+    pub fn get_heading_type(&self) -> HeadingType {
+        if self.heading_type == 0 {
+            HeadingType::HeadingTrue
+        } else {
+            HeadingType::HeadingMagnetic
+        }
+    }
+}
+
+/// Auxiliary state vector (AUX SV)
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct AuxiliaryStateVector {
+    #[deku(bits = "12")]
+    secondary_altitude: u16,
+
+    #[deku(bits = "28")]
+    reserved: u32,
+}
+
+impl AuxiliaryStateVector {
+    /// Secondary altitude (in feet)
+    pub fn get_secondary_altitude(&self) -> Option<i32> {
+        decode_altitude(self.secondary_altitude)
+    }
+}
+
+fn decode_altitude(value: u16) -> Option<i32> {
+    match value {
+        0 => None,
+        value => Some((value as i32 - 1) * 25 - 1000),
+    }
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum AddressQualifier {
+    ADSBWithICAOAddress,
+    ADSBWithSelfAssignedAddress,
+    TISBWithICAOAddress,
+    TISBWithTrackFileID,
+    SurfaceVehicle,
+    FixedADSBBeacon,
+    ADSRWithNonICAOAddress,
+    Reserved,
+}
+
+impl From<AddressQualifier> for AddressType {
+    fn from(value: AddressQualifier) -> Self {
+        match value {
+            AddressQualifier::ADSBWithICAOAddress => AddressType::ADSBWithICAOAddress,
+            AddressQualifier::ADSBWithSelfAssignedAddress => {
+                AddressType::ADSBWithSelfAssignedAddress
+            }
+            AddressQualifier::TISBWithICAOAddress => AddressType::TISBWithICAOAddress,
+            AddressQualifier::TISBWithTrackFileID => AddressType::TISBWithTrackFileID,
+            AddressQualifier::SurfaceVehicle => AddressType::SurfaceVehicle,
+            AddressQualifier::FixedADSBBeacon => AddressType::GroundStationBeacon,
+            // No GDL90 equivalent
+            AddressQualifier::ADSRWithNonICAOAddress => AddressType::Reserved1,
+            AddressQualifier::Reserved => AddressType::Reserved2,
+        }
+    }
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum AltitudeType {
+    Barometric,
+    Geometric,
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum AirGroundState {
+    AirborneSubsonic,
+    AirborneSupersonic,
+    OnGround,
+    Reserved,
+}
+
+#[derive(Ordinalize, Debug, PartialEq, Clone, Copy)]
+pub enum CallsignType {
+    Squawk,
+    FlightID,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AddressQualifier, AirGroundState, AltitudeType, CallsignType, UatAdsbPayload};
+    use crate::fisb::test::pack;
+    use crate::report::{
+        AddressType, EmergencyPriorityCode, EmitterCategory, FlightStage, HeadingType, NACp,
+        Report, NIC,
+    };
+
+    fn state_vector(air_ground_state: u32, north: u32, east: u32, vertical: u32) -> Vec<u8> {
+        let latitude = (51.5 * (1 << 23) as f32 / 180.) as u32;
+        let longitude = ((-0.5 * (1 << 23) as f32 / 180.) as i32 as u32) & 0xFFFFFF;
+
+        pack(&[
+            (latitude, 23),
+            (longitude, 24),
+            (0, 1),    // Barometric
+            (361, 12), // 8000ft
+            (8, 4),
+            (air_ground_state, 2),
+            (0, 1),
+            (north, 11),
+            (east, 11),
+            (vertical, 11),
+            (0x8, 4),
+        ])
+    }
+
+    #[test]
+    fn test_basic_payload() {
+        let mut data = pack(&[(0, 5), (0, 3), (0xABCDEF, 24)]);
+        data.extend(state_vector(0, 101, 0x400 | 101, 0x400 | 0x200 | 9));
+        data.push(0);
+
+        let payload = UatAdsbPayload::try_from(&data[..]).unwrap();
+        let header = payload.get_header();
+
+        assert_eq!(header.get_payload_type_code(), 0);
+        assert_eq!(
+            header.get_address_qualifier(),
+            AddressQualifier::ADSBWithICAOAddress
+        );
+        assert_eq!(header.get_address(), 0xABCDEF);
+        assert!(payload.get_mode_status().is_none());
+        assert!(payload.get_auxiliary_state_vector().is_none());
+
+        let sv = payload.get_state_vector().unwrap();
+        assert!((sv.get_latitude().unwrap() - 51.5).abs() < 0.0001);
+        assert!((sv.get_longitude().unwrap() + 0.5).abs() < 0.0001);
+        assert_eq!(sv.get_altitude_type(), AltitudeType::Barometric);
+        assert_eq!(sv.get_altitude(), Some(8000));
+        assert_eq!(sv.get_navigation_integrity_category(), NIC::Lt0_1NM);
+        assert_eq!(sv.get_air_ground_state(), AirGroundState::AirborneSubsonic);
+        assert_eq!(sv.get_north_velocity(), Some(100));
+        assert_eq!(sv.get_east_velocity(), Some(-100));
+        assert_eq!(sv.get_horizontal_velocity(), Some(141));
+        assert_eq!(sv.get_track(), Some((315., HeadingType::TrueTrackAngle)));
+        assert_eq!(sv.get_vertical_rate(), Some(-512));
+        assert_eq!(
+            sv.get_vertical_rate_source(),
+            Some(AltitudeType::Barometric)
+        );
+        assert!(sv.get_utc_coupled());
+    }
+
+    #[test]
+    fn test_long_payload() {
+        let mut data = pack(&[(1, 5), (1, 3), (0x123456, 24)]);
+        data.extend(state_vector(2, 11, (1 << 9) | 128, 0));
+
+        // Emitter category Light, call sign "N123AB"
+        let callsign = [1, 23, 1, 2, 3, 10, 11, 36, 36];
+        data.extend(pack(&[
+            (
+                (callsign[0] * 1600 + callsign[1] * 40 + callsign[2]) as u32,
+                16,
+            ),
+            (
+                (callsign[3] * 1600 + callsign[4] * 40 + callsign[5]) as u32,
+                16,
+            ),
+            (
+                (callsign[6] * 1600 + callsign[7] * 40 + callsign[8]) as u32,
+                16,
+            ),
+            (1, 3), // General emergency
+            (2, 3),
+            (3, 2),
+            (0, 6),
+            (0, 2),
+            (9, 4),
+            (2, 3),
+            (1, 1),
+            (0b1001_0010, 8),
+            (0, 16),
+        ]));
+        data.extend(pack(&[(401, 12), (0, 28)]));
+
+        let payload = UatAdsbPayload::try_from(&data[..]).unwrap();
+        assert_eq!(
+            payload.get_header().get_address_qualifier(),
+            AddressQualifier::ADSBWithSelfAssignedAddress
+        );
+
+        let sv = payload.get_state_vector().unwrap();
+        assert_eq!(sv.get_air_ground_state(), AirGroundState::OnGround);
+        assert_eq!(sv.get_horizontal_velocity(), Some(10));
+        assert_eq!(sv.get_track(), Some((90., HeadingType::TrueTrackAngle)));
+        assert_eq!(sv.get_north_velocity(), None);
+        assert_eq!(sv.get_vertical_rate(), None);
+
+        let ms = payload.get_mode_status().unwrap();
+        assert_eq!(ms.get_emitter_category(), EmitterCategory::Light);
+        assert_eq!(ms.get_callsign(), "N123AB");
+        assert_eq!(ms.get_callsign_type(), CallsignType::FlightID);
+        assert_eq!(
+            ms.get_emergency_priority_code(),
+            EmergencyPriorityCode::GeneralEmergency
+        );
+        assert_eq!(ms.get_uat_version(), 2);
+        assert_eq!(ms.get_source_integrity_level(), 3);
+        assert_eq!(
+            ms.get_navigation_accuracy_category_for_position(),
+            NACp::HFOMlt30mAndVFOMlt45m
+        );
+        assert_eq!(ms.get_navigation_accuracy_category_for_velocity(), 2);
+        assert!(ms.get_nic_baro());
+        assert!(ms.get_cdti());
+        assert!(!ms.get_acas());
+        assert!(ms.get_ident_active());
+        assert_eq!(ms.get_heading_type(), HeadingType::HeadingTrue);
+
+        let aux = payload.get_auxiliary_state_vector().unwrap();
+        assert_eq!(aux.get_secondary_altitude(), Some(9000));
+        assert_eq!(
+            payload.get_secondary_altitude_type(),
+            Some(AltitudeType::Geometric)
+        );
+    }
+
+    #[test]
+    fn test_invalid_length() {
+        assert!(UatAdsbPayload::try_from(&[0_u8; 20][..]).is_err());
+    }
+
+    #[test]
+    fn test_basic_payload_to_report() {
+        let mut data = pack(&[(0, 5), (2, 3), (0xABCDEF, 24)]);
+        data.extend(state_vector(0, 101, 0x400 | 101, 0x400 | 0x200 | 9));
+        data.push(0);
+
+        let payload = UatAdsbPayload::try_from(&data[..]).unwrap();
+        let report = Report::try_from(&payload).unwrap();
+
+        assert_eq!(report.participant_address, 0xABCDEF);
+        assert_eq!(report.get_address_type(), AddressType::TISBWithICAOAddress);
+        assert!((report.get_latitude() - 51.5).abs() < 0.0001);
+        assert!((report.get_longitude() + 0.5).abs() < 0.0001);
+        assert_eq!(report.get_altitude(), 8000);
+        assert_eq!(report.get_flight_stage(), FlightStage::Airborne);
+        assert_eq!(report.get_navigation_integrity_category(), NIC::Lt0_1NM);
+        assert_eq!(
+            report.get_navigation_accuracy_category_for_position(),
+            NACp::Unknown
+        );
+        assert_eq!(report.get_horizontal_velocity(), Some(141));
+        assert_eq!(report.get_vertical_velocity(), Some(-512));
+        assert_eq!(report.get_heading_type(), HeadingType::TrueTrackAngle);
+        assert_eq!(report.get_track_heading(), 315.);
+        assert_eq!(report.get_callsign().unwrap(), "        ");
+    }
+
+    #[test]
+    fn test_long_payload_to_report() {
+        let mut data = pack(&[(1, 5), (0, 3), (0x123456, 24)]);
+        let mut sv = state_vector(2, 11, (2 << 9) | 64, 0);
+        sv[5] |= 0x01; // Geometric altitude
+        data.extend(sv);
+        data.extend(pack(&[
+            ((3 * 1600 + 22 * 40 + 12) as u32, 16),  // Large, "MC"
+            ((18 * 1600 + 36 * 40 + 36) as u32, 16), // "I  "
+            ((36 * 1600 + 36 * 40 + 36) as u32, 16),
+            (0, 8),
+            (0, 8),
+            (10, 4),
+            (0, 4),
+            (0x02, 8),
+            (0, 16),
+        ]));
+        data.extend(pack(&[(401, 12), (0, 28)]));
+
+        let payload = UatAdsbPayload::try_from(&data[..]).unwrap();
+        let report = Report::try_from(&payload).unwrap();
+
+        assert_eq!(report.get_altitude(), 9000);
+        assert_eq!(report.get_flight_stage(), FlightStage::OnGround);
+        assert_eq!(report.get_horizontal_velocity(), Some(10));
+        assert_eq!(report.get_vertical_velocity(), None);
+        assert_eq!(report.get_heading_type(), HeadingType::HeadingMagnetic);
+        assert_eq!(report.get_track_heading(), 45.);
+        assert_eq!(report.get_emitter_category(), EmitterCategory::Large);
+        assert_eq!(
+            report.get_navigation_accuracy_category_for_position(),
+            NACp::HFOMlt10mAndVFOMlt15m
+        );
+        assert_eq!(report.get_callsign().unwrap(), "MCI     ");
+    }
+
+    #[test]
+    fn test_payload_without_state_vector() {
+        let mut data = pack(&[(11, 5), (0, 3), (0x123456, 24)]);
+        data.resize(34, 0);
+
+        let payload = UatAdsbPayload::try_from(&data[..]).unwrap();
+        assert!(payload.get_state_vector().is_none());
+        assert!(Report::try_from(&payload).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Uplink data message
+use crate::{
+    error::GDL90Error,
+    fisb::InformationFrames,
+    report::{lat_f32_to_u23, lat_long_f32_to_u32, lat_long_u32_to_f32, lat_u23_to_f32},
+};
+use deku::prelude::*;
+
+/// Length of the UAT uplink payload, in bytes
+pub const UPLINK_PAYLOAD_LENGTH: usize = 432;
+
+/// Length of the UAT ground uplink header at the start of the uplink payload, in bytes
+pub const UPLINK_HEADER_LENGTH: usize = 8;
+
+pub(crate) const TIME_OF_RECEPTION_INVALID: u32 = 0xFFFFFF;
+
+/// Time of Reception from its 24-bit field, `None` if invalid
+pub(crate) fn decode_time_of_reception(value: u32) -> Option<u32> {
+    if value == TIME_OF_RECEPTION_INVALID {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// 24-bit Time of Reception field, saturating below the invalid value
+pub(crate) fn encode_time_of_reception(value: Option<u32>) -> u32 {
+    if let Some(value) = value {
+        value.min(TIME_OF_RECEPTION_INVALID - 1)
+    } else {
+        TIME_OF_RECEPTION_INVALID
+    }
+}
+
+#[derive(DekuRead, DekuWrite, Debug, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct UplinkData {
+    #[deku(bits = "24", endian = "little")]
+    time_of_reception: u32,
+
+    #[deku(count = "UPLINK_PAYLOAD_LENGTH")]
+    uplink_payload: Vec<u8>,
+}
+
+impl Default for UplinkData {
+    fn default() -> Self {
+        Self {
+            time_of_reception: TIME_OF_RECEPTION_INVALID,
+            uplink_payload: vec![0; UPLINK_PAYLOAD_LENGTH],
+        }
+    }
+}
+
+impl UplinkData {
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn get_time_of_reception(&self) -> Option<u32> {
+        decode_time_of_reception(self.time_of_reception)
+    }
+
+    /// Time of Reception (in 80ns units since the start of the UTC second)
+    pub fn set_time_of_reception(&mut self, value: Option<u32>) {
+        self.time_of_reception = encode_time_of_reception(value);
+    }
+
+    /// UAT uplink payload
+    pub fn get_uplink_payload(&self) -> &[u8] {
+        &self.uplink_payload
+    }
+
+    /// UAT uplink payload
+    pub fn set_uplink_payload(&mut self, value: [u8; UPLINK_PAYLOAD_LENGTH]) {
+        self.uplink_payload = value.to_vec();
+    }
+
+    /// UAT ground uplink header
+    pub fn get_uplink_header(&self) -> Result<UplinkHeader, GDL90Error> {
+        Ok(UplinkHeader::try_from(
+            &self.uplink_payload[..UPLINK_HEADER_LENGTH],
+        )?)
+    }
+
+    /// UAT ground uplink header
+    pub fn set_uplink_header(&mut self, value: &UplinkHeader) -> Result<(), GDL90Error> {
+        let header = value.to_bytes()?;
+        self.uplink_payload[..UPLINK_HEADER_LENGTH].copy_from_slice(&header);
+        Ok(())
+    }
+
+    /// Application data following the UAT ground uplink header
+    pub fn get_application_data(&self) -> &[u8] {
+        &self.uplink_payload[UPLINK_HEADER_LENGTH..]
+    }
+
+    /// Information frames in the application data
+    pub fn get_information_frames(&self) -> InformationFrames<'_> {
+        InformationFrames::new(self.get_application_data())
+    }
+}
+
+/// UAT ground uplink header
+#[derive(DekuRead, DekuWrite, Debug, Default, PartialEq)]
+#[deku(bit_order = "msb", endian = "big")]
+pub struct UplinkHeader {
+    #[deku(bits = "23")]
+    latitude: u32,
+
+    #[deku(bits = "24")]
+    longitude: u32,
+
+    #[deku(bits = "1")]
+    position_valid: bool,
+
+    #[deku(bits = "1")]
+    utc_coupled: bool,
+
+    #[deku(bits = "1")]
+    reserved1: bool,
+
+    #[deku(bits = "1")]
+    app_data_valid: bool,
+
+    #[deku(bits = "5")]
+    slot_id: u8,
+
+    #[deku(bits = "4")]
+    tisb_site_id: u8,
+
+    #[deku(bits = "4")]
+    reserved2: u8,
+}
+
+impl UplinkHeader {
+    /// Ground station latitude (in degrees)
+    pub fn get_latitude(&self) -> f32 {
+        lat_u23_to_f32(self.latitude)
+    }
+
+    /// Ground station latitude (in degrees)
+    pub fn set_latitude(&mut self, value: f32) {
+        self.latitude = lat_f32_to_u23(value);
+    }
+
+    /// Ground station longitude (in degrees)
+    pub fn get_longitude(&self) -> f32 {
+        lat_long_u32_to_f32(self.longitude)
+    }
+
+    /// Ground station longitude (in degrees)
+    pub fn set_longitude(&mut self, value: f32) {
+        self.longitude = lat_long_f32_to_u32(value);
+    }
+
+    /// Ground station position is valid
+    pub fn get_position_valid(&self) -> bool {
+        self.position_valid
+    }
+
+    /// Ground station position is valid
+    pub fn set_position_valid(&mut self, value: bool) {
+        self.position_valid = value;
+    }
+
+    /// Ground station is UTC coupled
+    pub fn get_utc_coupled(&self) -> bool {
+        self.utc_coupled
+    }
+
+    /// Ground station is UTC coupled
+    pub fn set_utc_coupled(&mut self, value: bool) {
+        self.utc_coupled = value;
+    }
+
+    /// Application data is valid
+    pub fn get_app_data_valid(&self) -> bool {
+        self.app_data_valid
+    }
+
+    /// Application data is valid
+    pub fn set_app_data_valid(&mut self, value: bool) {
+        self.app_data_valid = value;
+    }
+
+    /// Ground station slot ID (0 - 31)
+    pub fn get_slot_id(&self) -> u8 {
+        self.slot_id
+    }
+
+    /// Ground station slot ID (0 - 31)
+    pub fn set_slot_id(&mut self, value: u8) {
+        self.slot_id = value & 0x1F;
+    }
+
+    /// TIS-B site ID (0 - 15)
+    pub fn get_tisb_site_id(&self) -> u8 {
+        self.tisb_site_id
+    }
+
+    /// TIS-B site ID (0 - 15)
+    pub fn set_tisb_site_id(&mut self, value: u8) {
+        self.tisb_site_id = value & 0x0F;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use deku::DekuContainerWrite;
+
+    use super::{UplinkData, UplinkHeader, UPLINK_PAYLOAD_LENGTH};
+    use crate::{
+        message::{create_message, read_message},
+        payload::Payload,
+    };
+
+    #[test]
+    fn test_encode_decode() {
+        let mut data = vec![0x56, 0x34, 0x12];
+        data.extend((0..UPLINK_PAYLOAD_LENGTH).map(|i| i as u8));
+
+        let uplink = UplinkData::try_from(&data[..]).unwrap();
+
+        assert_eq!(uplink.get_time_of_reception(), Some(0x123456));
+        assert_eq!(uplink.get_uplink_payload()[..], data[3..]);
+
+        let mut new_uplink = UplinkData::default();
+        new_uplink.set_time_of_reception(Some(0x123456));
+        new_uplink.set_uplink_payload(uplink.get_uplink_payload().try_into().unwrap());
+
+        let encode = new_uplink.to_bytes().unwrap();
+
+        assert_eq!(uplink, new_uplink);
+        assert_eq!(&data[..], &encode[..]);
+    }
+
+    #[test]
+    fn test_invalid_time_of_reception() {
+        let uplink = UplinkData::default();
+        assert_eq!(uplink.get_time_of_reception(), None);
+        assert_eq!(&uplink.to_bytes().unwrap()[..3], &[0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut uplink = UplinkData::default();
+        uplink.set_time_of_reception(Some(1000));
+        uplink.set_uplink_payload([0x7E; UPLINK_PAYLOAD_LENGTH]);
+
+        let encoded = create_message(&Payload::UplinkData(uplink))
+            .unwrap()
+            .encode();
+        let message = read_message(&encoded[..]).unwrap();
+
+        if let Ok(Payload::UplinkData(payload)) = message.get_payload() {
+            assert_eq!(payload.get_time_of_reception(), Some(1000));
+            assert_eq!(payload.get_uplink_payload(), [0x7E; UPLINK_PAYLOAD_LENGTH]);
+        } else {
+            panic!("Unexpected payload type");
+        }
+    }
+
+    #[test]
+    fn test_uplink_header() {
+        let data = [0x49, 0x3E, 0x93, 0xFF, 0x49, 0xF7, 0xA7, 0x30];
+        let header = UplinkHeader::try_from(&data[..]).unwrap();
+
+        assert_eq!(header.get_latitude(), 51.499_98);
+        assert_eq!(header.get_longitude(), -0.499_985_22);
+        assert!(header.get_position_valid());
+        assert!(header.get_utc_coupled());
+        assert!(header.get_app_data_valid());
+        assert_eq!(header.get_slot_id(), 7);
+        assert_eq!(header.get_tisb_site_id(), 3);
+
+        let mut new_header = UplinkHeader::default();
+        new_header.set_latitude(header.get_latitude());
+        new_header.set_longitude(header.get_longitude());
+        new_header.set_position_valid(true);
+        new_header.set_utc_coupled(true);
+        new_header.set_app_data_valid(true);
+        new_header.set_slot_id(7);
+        new_header.set_tisb_site_id(3);
+
+        let mut uplink = UplinkData::default();
+        uplink.set_uplink_header(&new_header).unwrap();
+
+        assert_eq!(header, new_header);
+        assert_eq!(&data[..], &uplink.get_uplink_payload()[..8]);
+        assert_eq!(uplink.get_uplink_header().unwrap(), header);
+    }
+
+    #[test]
+    fn test_uplink_header_southern_hemisphere() {
+        let mut header = UplinkHeader::default();
+        header.set_latitude(-33.9);
+        header.set_longitude(151.2);
+
+        assert!((header.get_latitude() + 33.9).abs() < 0.0001);
+        assert!((header.get_longitude() - 151.2).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,1156 @@
+//! Serde support (requires the `serde` feature)
+//!
+//! Payloads are serialised through views holding engineering units (degrees, feet,
+//! knots and enums by name) rather than the raw bit fields, and deserialised back
+//! through the payload setters so the result can be encoded again.
+use crate::{
+    extended::{
+        AHRSHeadingType, ExtendedX65SubMessage, GeometricAltitudeDatum, IDMessage, InternetPolicy,
+        AHRS,
+    },
+    geometric::OwnershipGeometricAltitude,
+    heartbeat::HeartBeat,
+    initialization::Initialization,
+    levil::LevilAHRS,
+    passthrough::{BasicUatReport, LongUatReport},
+    payload::Payload,
+    report::{
+        AddressType, EmergencyPriorityCode, EmitterCategory, FlightStage, HeadingType, NACp,
+        Report, ReportType, TrafficAlertStatus, NIC,
+    },
+    stratux::{GpsFix, StratuxHeartbeat, StratuxStatus, StratuxTower},
+    terrain::HeightAboveTerrain,
+    uplink::UplinkData,
+};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Conversion between a payload and its serialised view
+trait View<T>: Sized {
+    fn from_payload(payload: &T) -> Result<Self, String>;
+    fn into_payload(self) -> Result<T, String>;
+}
+
+macro_rules! serde_view {
+    ($type:ty, $view:ty) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                <$view>::from_payload(self)
+                    .map_err(ser::Error::custom)?
+                    .serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <$view>::deserialize(deserializer)?
+                    .into_payload()
+                    .map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+serde_view!(HeartBeat, HeartBeatView);
+serde_view!(Initialization, InitializationView);
+serde_view!(UplinkData, UplinkDataView);
+serde_view!(Report, ReportView);
+serde_view!(OwnershipGeometricAltitude, OwnershipGeometricAltitudeView);
+serde_view!(HeightAboveTerrain, HeightAboveTerrainView);
+serde_view!(BasicUatReport, UatReportView);
+serde_view!(LongUatReport, UatReportView);
+serde_view!(IDMessage, IDMessageView);
+serde_view!(AHRS, AHRSView);
+serde_view!(StratuxHeartbeat, StratuxHeartbeatView);
+serde_view!(StratuxStatus, StratuxStatusView);
+serde_view!(StratuxTower, StratuxTowerView);
+serde_view!(LevilAHRS, LevilAHRSView);
+
+#[derive(Serialize, Deserialize)]
+struct HeartBeatView {
+    gps_pos_valid: bool,
+    maint_req: bool,
+    ident: bool,
+    addr_type: bool,
+    gps_batt_low: bool,
+    ratcs: bool,
+    uat_initialized: bool,
+    csa_requested: bool,
+    csa_not_available: bool,
+    utc_ok: bool,
+    /// Seconds since 0000Z
+    time_stamp: u32,
+    /// Uplink messages received in the previous second
+    uplink_count: u8,
+    /// Basic and long messages received in the previous second
+    basic_long_count: u16,
+}
+
+impl View<HeartBeat> for HeartBeatView {
+    fn from_payload(payload: &HeartBeat) -> Result<Self, String> {
+        Ok(Self {
+            gps_pos_valid: payload.gps_pos_valid,
+            maint_req: payload.maint_req,
+            ident: payload.ident,
+            addr_type: payload.addr_type,
+            gps_batt_low: payload.gps_batt_low,
+            ratcs: payload.ratcs,
+            uat_initialized: payload.uat_initialized,
+            csa_requested: payload.csa_requested,
+            csa_not_available: payload.csa_not_available,
+            utc_ok: payload.utc_ok,
+            time_stamp: payload.get_time_stamp(),
+            uplink_count: payload.get_uplink_count(),
+            basic_long_count: payload.get_basic_long_count(),
+        })
+    }
+
+    fn into_payload(self) -> Result<HeartBeat, String> {
+        let mut payload = HeartBeat::default();
+        payload.gps_pos_valid = self.gps_pos_valid;
+        payload.maint_req = self.maint_req;
+        payload.ident = self.ident;
+        payload.addr_type = self.addr_type;
+        payload.gps_batt_low = self.gps_batt_low;
+        payload.ratcs = self.ratcs;
+        payload.uat_initialized = self.uat_initialized;
+        payload.csa_requested = self.csa_requested;
+        payload.csa_not_available = self.csa_not_available;
+        payload.utc_ok = self.utc_ok;
+        payload.set_time_stamp(self.time_stamp);
+        if self.uplink_count > 0x1F || self.basic_long_count > 0x03FF {
+            return Err(format!(
+                "Message counts {} and {} out of range",
+                self.uplink_count, self.basic_long_count
+            ));
+        }
+        payload.set_uplink_count(self.uplink_count);
+        payload.set_basic_long_count(self.basic_long_count);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct InitializationView {
+    audio_test: bool,
+    audio_inhibit: bool,
+    cdti_ok: bool,
+    csa_audio_disable: bool,
+    csa_disable: bool,
+}
+
+impl View<Initialization> for InitializationView {
+    fn from_payload(payload: &Initialization) -> Result<Self, String> {
+        Ok(Self {
+            audio_test: payload.get_audio_test(),
+            audio_inhibit: payload.get_audio_inhibit(),
+            cdti_ok: payload.get_cdti_ok(),
+            csa_audio_disable: payload.get_csa_audio_disable(),
+            csa_disable: payload.get_csa_disable(),
+        })
+    }
+
+    fn into_payload(self) -> Result<Initialization, String> {
+        let mut payload = Initialization::default();
+        payload.set_audio_test(self.audio_test);
+        payload.set_audio_inhibit(self.audio_inhibit);
+        payload.set_cdti_ok(self.cdti_ok);
+        payload.set_csa_audio_disable(self.csa_audio_disable);
+        payload.set_csa_disable(self.csa_disable);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UplinkDataView {
+    /// 80ns units since the start of the UTC second
+    time_of_reception: Option<u32>,
+    uplink_payload: Vec<u8>,
+}
+
+impl View<UplinkData> for UplinkDataView {
+    fn from_payload(payload: &UplinkData) -> Result<Self, String> {
+        Ok(Self {
+            time_of_reception: payload.get_time_of_reception(),
+            uplink_payload: payload.get_uplink_payload().to_vec(),
+        })
+    }
+
+    fn into_payload(self) -> Result<UplinkData, String> {
+        let mut payload = UplinkData::default();
+        payload.set_time_of_reception(self.time_of_reception);
+        payload.set_uplink_payload(to_array(self.uplink_payload)?);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReportView {
+    traffic_alert_status: TrafficAlertStatus,
+    address_type: AddressType,
+    participant_address: u32,
+    /// Degrees
+    latitude: f32,
+    /// Degrees
+    longitude: f32,
+    /// Pressure altitude in feet
+    altitude: Option<i32>,
+    heading_type: HeadingType,
+    report_type: ReportType,
+    flight_stage: FlightStage,
+    navigation_integrity_category: NIC,
+    navigation_accuracy_category_for_position: NACp,
+    /// Knots
+    horizontal_velocity: Option<u16>,
+    /// Feet per minute
+    vertical_velocity: Option<i16>,
+    /// Degrees
+    track_heading: f32,
+    emitter_category: EmitterCategory,
+    callsign: String,
+    emergency_priority_code: EmergencyPriorityCode,
+}
+
+impl View<Report> for ReportView {
+    fn from_payload(payload: &Report) -> Result<Self, String> {
+        Ok(Self {
+            traffic_alert_status: payload.get_traffic_alert_status(),
+            address_type: payload.get_address_type(),
+            participant_address: payload.participant_address,
+            latitude: payload.get_latitude(),
+            longitude: payload.get_longitude(),
+            altitude: payload.is_altitude_valid().then(|| payload.get_altitude()),
+            heading_type: payload.get_heading_type(),
+            report_type: payload.get_report_type(),
+            flight_stage: payload.get_flight_stage(),
+            navigation_integrity_category: payload.get_navigation_integrity_category(),
+            navigation_accuracy_category_for_position: payload
+                .get_navigation_accuracy_category_for_position(),
+            horizontal_velocity: payload.get_horizontal_velocity(),
+            vertical_velocity: payload.get_vertical_velocity(),
+            track_heading: payload.get_track_heading(),
+            emitter_category: payload.get_emitter_category(),
+            callsign: payload
+                .get_callsign()
+                .map_err(|err| err.to_string())?
+                .trim_end()
+                .to_owned(),
+            emergency_priority_code: payload.get_emergency_priority_code(),
+        })
+    }
+
+    fn into_payload(self) -> Result<Report, String> {
+        if self.callsign.len() > 8 {
+            return Err(format!("Callsign {} is longer than 8 bytes", self.callsign));
+        }
+
+        let mut callsign = [b' '; 8];
+        callsign[..self.callsign.len()].copy_from_slice(self.callsign.as_bytes());
+
+        let mut payload = Report::default();
+        payload.set_traffic_alert_status(self.traffic_alert_status);
+        payload.set_address_type(self.address_type);
+        payload.participant_address = self.participant_address;
+        payload.set_latitude(self.latitude);
+        payload.set_longitude(self.longitude);
+        match self.altitude {
+            Some(altitude) => payload.set_altitude(altitude),
+            None => payload.set_altitude_invalid(),
+        }
+        payload.set_heading_type(self.heading_type);
+        payload.set_report_type(self.report_type);
+        payload.set_flight_stage(self.flight_stage);
+        payload.set_navigation_integrity_category(self.navigation_integrity_category);
+        payload.set_navigation_accuracy_category_for_position(
+            self.navigation_accuracy_category_for_position,
+        );
+        payload.set_horizontal_velocity(self.horizontal_velocity);
+        // The setter takes 64 fpm units
+        payload.set_vertical_velocity(self.vertical_velocity.map(|value| value / 64));
+        payload.set_track_heading(self.track_heading);
+        payload.set_emitter_category(self.emitter_category);
+        payload.set_callsign(callsign);
+        payload.set_emergency_priority_code(self.emergency_priority_code);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct OwnershipGeometricAltitudeView {
+    /// Feet
+    ownship_geo_altitude: i32,
+    vertical_warning_indicator: bool,
+    /// Meters
+    vertical_figure_of_merit: Option<u16>,
+}
+
+impl View<OwnershipGeometricAltitude> for OwnershipGeometricAltitudeView {
+    fn from_payload(payload: &OwnershipGeometricAltitude) -> Result<Self, String> {
+        Ok(Self {
+            ownship_geo_altitude: payload.get_ownship_geo_altitude(),
+            vertical_warning_indicator: payload.get_vertical_warning_indicator(),
+            vertical_figure_of_merit: payload.get_vertical_figure_of_merit(),
+        })
+    }
+
+    fn into_payload(self) -> Result<OwnershipGeometricAltitude, String> {
+        let mut payload = OwnershipGeometricAltitude::default();
+        payload.set_ownship_geo_altitude(
+            u32::try_from(self.ownship_geo_altitude).map_err(|err| err.to_string())?,
+        );
+        payload.set_vertical_warning_indicator(self.vertical_warning_indicator);
+        payload.set_vertical_figure_of_merit(self.vertical_figure_of_merit);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HeightAboveTerrainView {
+    /// Feet
+    height_above_terrain: Option<i16>,
+}
+
+impl View<HeightAboveTerrain> for HeightAboveTerrainView {
+    fn from_payload(payload: &HeightAboveTerrain) -> Result<Self, String> {
+        Ok(Self {
+            height_above_terrain: payload.get_height_above_terrain(),
+        })
+    }
+
+    fn into_payload(self) -> Result<HeightAboveTerrain, String> {
+        let mut payload = HeightAboveTerrain::default();
+        payload.set_height_above_terrain(self.height_above_terrain);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UatReportView {
+    /// 80ns units since the start of the UTC second
+    time_of_reception: Option<u32>,
+    payload: Vec<u8>,
+}
+
+impl View<BasicUatReport> for UatReportView {
+    fn from_payload(payload: &BasicUatReport) -> Result<Self, String> {
+        Ok(Self {
+            time_of_reception: payload.get_time_of_reception(),
+            payload: payload.get_payload().to_vec(),
+        })
+    }
+
+    fn into_payload(self) -> Result<BasicUatReport, String> {
+        let mut payload = BasicUatReport::default();
+        payload.set_time_of_reception(self.time_of_reception);
+        payload.set_payload(to_array(self.payload)?);
+        Ok(payload)
+    }
+}
+
+impl View<LongUatReport> for UatReportView {
+    fn from_payload(payload: &LongUatReport) -> Result<Self, String> {
+        Ok(Self {
+            time_of_reception: payload.get_time_of_reception(),
+            payload: payload.get_payload().to_vec(),
+        })
+    }
+
+    fn into_payload(self) -> Result<LongUatReport, String> {
+        let mut payload = LongUatReport::default();
+        payload.set_time_of_reception(self.time_of_reception);
+        payload.set_payload(to_array(self.payload)?);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct IDMessageView {
+    version: u8,
+    device_serial_number: u64,
+    device_name: String,
+    device_long_name: String,
+    geometric_altitude_datum: GeometricAltitudeDatum,
+    internet_policy: InternetPolicy,
+}
+
+impl View<IDMessage> for IDMessageView {
+    fn from_payload(payload: &IDMessage) -> Result<Self, String> {
+        Ok(Self {
+            version: payload.version,
+            device_serial_number: payload.device_serial_number,
+            device_name: payload.get_device_name().map_err(|err| err.to_string())?,
+            device_long_name: payload
+                .get_device_long_name()
+                .map_err(|err| err.to_string())?,
+            geometric_altitude_datum: payload.get_geometric_altitude_datum(),
+            internet_policy: payload.get_internet_policy(),
+        })
+    }
+
+    fn into_payload(self) -> Result<IDMessage, String> {
+        let mut payload = IDMessage::default();
+        payload.version = self.version;
+        payload.device_serial_number = self.device_serial_number;
+        payload
+            .set_device_name(&self.device_name)
+            .map_err(|err| format!("{err:?}"))?;
+        payload
+            .set_device_long_name(&self.device_long_name)
+            .map_err(|err| format!("{err:?}"))?;
+        payload.set_geometric_altitude_datum(self.geometric_altitude_datum);
+        payload.set_internet_policy(self.internet_policy);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+struct AHRSView {
+    /// Degrees
+    roll: Option<f32>,
+    /// Degrees
+    pitch: Option<f32>,
+    heading_type: AHRSHeadingType,
+    /// Degrees
+    heading: Option<f32>,
+    /// Knots
+    indicated_airspeed: Option<u16>,
+    /// Knots
+    true_airspeed: Option<u16>,
+}
+
+impl View<AHRS> for AHRSView {
+    fn from_payload(payload: &AHRS) -> Result<Self, String> {
+        Ok(Self {
+            roll: payload.get_roll(),
+            pitch: payload.get_pitch(),
+            heading_type: payload.get_heading_type(),
+            heading: payload.get_heading(),
+            indicated_airspeed: payload.get_indicated_airspeed(),
+            true_airspeed: payload.get_true_airspeed(),
+        })
+    }
+
+    fn into_payload(self) -> Result<AHRS, String> {
+        let mut payload = AHRS::default();
+        payload.set_roll(self.roll);
+        payload.set_pitch(self.pitch);
+        payload.set_heading_type(self.heading_type);
+        payload.set_heading(self.heading);
+        payload.set_indicated_airspeed(self.indicated_airspeed);
+        payload.set_true_airspeed(self.true_airspeed);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "ExtendedX65SubMessage")]
+#[allow(clippy::upper_case_acronyms)]
+enum ExtendedX65SubMessageRef<'a> {
+    AHRS(&'a AHRS),
+    IDMessage(&'a IDMessage),
+    Unknown { sub_id: u8, data: &'a [u8] },
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "ExtendedX65SubMessage")]
+#[allow(clippy::upper_case_acronyms)]
+enum ExtendedX65SubMessageView {
+    AHRS(AHRS),
+    IDMessage(IDMessage),
+    Unknown { sub_id: u8, data: Vec<u8> },
+}
+
+impl Serialize for ExtendedX65SubMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ExtendedX65SubMessage::AHRS(payload) => ExtendedX65SubMessageRef::AHRS(payload),
+            ExtendedX65SubMessage::IDMessage(payload) => {
+                ExtendedX65SubMessageRef::IDMessage(payload)
+            }
+            ExtendedX65SubMessage::Unknown(sub_id, data) => ExtendedX65SubMessageRef::Unknown {
+                sub_id: *sub_id,
+                data,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtendedX65SubMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            match ExtendedX65SubMessageView::deserialize(deserializer)? {
+                ExtendedX65SubMessageView::AHRS(payload) => ExtendedX65SubMessage::AHRS(payload),
+                ExtendedX65SubMessageView::IDMessage(payload) => {
+                    ExtendedX65SubMessage::IDMessage(payload)
+                }
+                ExtendedX65SubMessageView::Unknown { sub_id, data } => {
+                    ExtendedX65SubMessage::Unknown(sub_id, data)
+                }
+            },
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StratuxHeartbeatView {
+    protocol_version: u8,
+    gps_valid: bool,
+    ahrs_valid: bool,
+}
+
+impl View<StratuxHeartbeat> for StratuxHeartbeatView {
+    fn from_payload(payload: &StratuxHeartbeat) -> Result<Self, String> {
+        Ok(Self {
+            protocol_version: payload.get_protocol_version(),
+            gps_valid: payload.get_gps_valid(),
+            ahrs_valid: payload.get_ahrs_valid(),
+        })
+    }
+
+    fn into_payload(self) -> Result<StratuxHeartbeat, String> {
+        let mut payload = StratuxHeartbeat::default();
+        payload.set_protocol_version(self.protocol_version);
+        payload.set_gps_valid(self.gps_valid);
+        payload.set_ahrs_valid(self.ahrs_valid);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StratuxStatusView {
+    firmware_version: [u8; 4],
+    hardware_revision: Option<u32>,
+    ahrs_enabled: bool,
+    gps_enabled: bool,
+    es_enabled: bool,
+    uat_enabled: bool,
+    pressure_altitude_valid: bool,
+    ahrs_valid: bool,
+    gps_fix: GpsFix,
+    imu_connected: bool,
+    radios: u8,
+    satellites_locked: u8,
+    satellites_tracked: u8,
+    uat_traffic_targets: u16,
+    es_traffic_targets: u16,
+    uat_messages_per_minute: u16,
+    es_messages_per_minute: u16,
+    /// Degrees Celsius
+    cpu_temperature: Option<f32>,
+    towers: Vec<StratuxTower>,
+}
+
+impl View<StratuxStatus> for StratuxStatusView {
+    fn from_payload(payload: &StratuxStatus) -> Result<Self, String> {
+        Ok(Self {
+            firmware_version: payload.get_firmware_version(),
+            hardware_revision: payload.get_hardware_revision(),
+            ahrs_enabled: payload.get_ahrs_enabled(),
+            gps_enabled: payload.get_gps_enabled(),
+            es_enabled: payload.get_es_enabled(),
+            uat_enabled: payload.get_uat_enabled(),
+            pressure_altitude_valid: payload.get_pressure_altitude_valid(),
+            ahrs_valid: payload.get_ahrs_valid(),
+            gps_fix: payload.get_gps_fix(),
+            imu_connected: payload.get_imu_connected(),
+            radios: payload.get_radios(),
+            satellites_locked: payload.get_satellites_locked(),
+            satellites_tracked: payload.get_satellites_tracked(),
+            uat_traffic_targets: payload.get_uat_traffic_targets(),
+            es_traffic_targets: payload.get_es_traffic_targets(),
+            uat_messages_per_minute: payload.get_uat_messages_per_minute(),
+            es_messages_per_minute: payload.get_es_messages_per_minute(),
+            cpu_temperature: payload.get_cpu_temperature(),
+            towers: payload.get_towers().to_vec(),
+        })
+    }
+
+    fn into_payload(self) -> Result<StratuxStatus, String> {
+        let mut payload = StratuxStatus::default();
+        payload.set_firmware_version(self.firmware_version);
+        payload.set_hardware_revision(self.hardware_revision);
+        payload.set_ahrs_enabled(self.ahrs_enabled);
+        payload.set_gps_enabled(self.gps_enabled);
+        payload.set_es_enabled(self.es_enabled);
+        payload.set_uat_enabled(self.uat_enabled);
+        payload.set_pressure_altitude_valid(self.pressure_altitude_valid);
+        payload.set_ahrs_valid(self.ahrs_valid);
+        payload.set_gps_fix(self.gps_fix);
+        payload.set_imu_connected(self.imu_connected);
+        payload.set_radios(self.radios);
+        payload.set_satellites_locked(self.satellites_locked);
+        payload.set_satellites_tracked(self.satellites_tracked);
+        payload.set_uat_traffic_targets(self.uat_traffic_targets);
+        payload.set_es_traffic_targets(self.es_traffic_targets);
+        payload.set_uat_messages_per_minute(self.uat_messages_per_minute);
+        payload.set_es_messages_per_minute(self.es_messages_per_minute);
+        payload.set_cpu_temperature(self.cpu_temperature);
+        payload.set_towers(self.towers);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StratuxTowerView {
+    /// Degrees
+    latitude: f32,
+    /// Degrees
+    longitude: f32,
+}
+
+impl View<StratuxTower> for StratuxTowerView {
+    fn from_payload(payload: &StratuxTower) -> Result<Self, String> {
+        Ok(Self {
+            latitude: payload.get_latitude(),
+            longitude: payload.get_longitude(),
+        })
+    }
+
+    fn into_payload(self) -> Result<StratuxTower, String> {
+        let mut payload = StratuxTower::default();
+        payload.set_latitude(self.latitude);
+        payload.set_longitude(self.longitude);
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LevilAHRSView {
+    /// Degrees
+    roll: Option<f32>,
+    /// Degrees
+    pitch: Option<f32>,
+    /// Degrees
+    heading: Option<f32>,
+    /// Degrees
+    slip_skid: Option<f32>,
+    /// Degrees per second
+    yaw_rate: Option<f32>,
+    /// G
+    g_load: Option<f32>,
+    /// Knots
+    indicated_airspeed: Option<i16>,
+    /// Feet
+    pressure_altitude: Option<i32>,
+    /// Feet per minute
+    vertical_speed: Option<i16>,
+}
+
+impl View<LevilAHRS> for LevilAHRSView {
+    fn from_payload(payload: &LevilAHRS) -> Result<Self, String> {
+        Ok(Self {
+            roll: payload.get_roll(),
+            pitch: payload.get_pitch(),
+            heading: payload.get_heading(),
+            slip_skid: payload.get_slip_skid(),
+            yaw_rate: payload.get_yaw_rate(),
+            g_load: payload.get_g_load(),
+            indicated_airspeed: payload.get_indicated_airspeed(),
+            pressure_altitude: payload.get_pressure_altitude(),
+            vertical_speed: payload.get_vertical_speed(),
+        })
+    }
+
+    fn into_payload(self) -> Result<LevilAHRS, String> {
+        let mut payload = LevilAHRS::default();
+        payload.set_roll(self.roll);
+        payload.set_pitch(self.pitch);
+        payload.set_heading(self.heading);
+        payload.set_slip_skid(self.slip_skid);
+        payload.set_yaw_rate(self.yaw_rate);
+        payload.set_g_load(self.g_load);
+        payload.set_indicated_airspeed(self.indicated_airspeed);
+        payload.set_pressure_altitude(self.pressure_altitude);
+        payload.set_vertical_speed(self.vertical_speed);
+        Ok(payload)
+    }
+}
+
+/// [Payload::Custom] is serialised as [Payload::Unknown], holding its encoded data
+#[derive(Serialize)]
+#[serde(rename = "Payload")]
+enum PayloadRef<'a> {
+    HeartBeat(&'a HeartBeat),
+    Initialization(&'a Initialization),
+    UplinkData(&'a UplinkData),
+    OwnershipReport(&'a Report),
+    TrafficReport(&'a Report),
+    OwnershipGeometricAltitude(&'a OwnershipGeometricAltitude),
+    HeightAboveTerrain(&'a HeightAboveTerrain),
+    BasicUatReport(&'a BasicUatReport),
+    LongUatReport(&'a LongUatReport),
+    ExtendedX65(&'a ExtendedX65SubMessage),
+    StratuxHeartbeat(&'a StratuxHeartbeat),
+    StratuxStatus(&'a StratuxStatus),
+    LevilAHRS(&'a LevilAHRS),
+    Unknown { message_id: u8, data: Vec<u8> },
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Payload")]
+enum PayloadView {
+    HeartBeat(HeartBeat),
+    Initialization(Initialization),
+    UplinkData(UplinkData),
+    OwnershipReport(Report),
+    TrafficReport(Report),
+    OwnershipGeometricAltitude(OwnershipGeometricAltitude),
+    HeightAboveTerrain(HeightAboveTerrain),
+    BasicUatReport(BasicUatReport),
+    LongUatReport(LongUatReport),
+    ExtendedX65(ExtendedX65SubMessage),
+    StratuxHeartbeat(StratuxHeartbeat),
+    StratuxStatus(StratuxStatus),
+    LevilAHRS(LevilAHRS),
+    Unknown { message_id: u8, data: Vec<u8> },
+}
+
+impl Serialize for Payload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Payload::HeartBeat(payload) => PayloadRef::HeartBeat(payload),
+            Payload::Initialization(payload) => PayloadRef::Initialization(payload),
+            Payload::UplinkData(payload) => PayloadRef::UplinkData(payload),
+            Payload::OwnershipReport(payload) => PayloadRef::OwnershipReport(payload),
+            Payload::TrafficReport(payload) => PayloadRef::TrafficReport(payload),
+            Payload::OwnershipGeometricAltitude(payload) => {
+                PayloadRef::OwnershipGeometricAltitude(payload)
+            }
+            Payload::HeightAboveTerrain(payload) => PayloadRef::HeightAboveTerrain(payload),
+            Payload::BasicUatReport(payload) => PayloadRef::BasicUatReport(payload),
+            Payload::LongUatReport(payload) => PayloadRef::LongUatReport(payload),
+            Payload::ExtendedX65(payload) => PayloadRef::ExtendedX65(payload),
+            Payload::StratuxHeartbeat(payload) => PayloadRef::StratuxHeartbeat(payload),
+            Payload::StratuxStatus(payload) => PayloadRef::StratuxStatus(payload),
+            Payload::LevilAHRS(payload) => PayloadRef::LevilAHRS(payload),
+            Payload::Custom(payload) => PayloadRef::Unknown {
+                message_id: payload.get_message_id(),
+                data: payload
+                    .to_bytes()
+                    .map_err(|err| ser::Error::custom(format!("{err:?}")))?,
+            },
+            Payload::Unknown(message_id, data) => PayloadRef::Unknown {
+                message_id: *message_id,
+                data: data.clone(),
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Payload {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match PayloadView::deserialize(deserializer)? {
+            PayloadView::HeartBeat(payload) => Payload::HeartBeat(payload),
+            PayloadView::Initialization(payload) => Payload::Initialization(payload),
+            PayloadView::UplinkData(payload) => Payload::UplinkData(payload),
+            PayloadView::OwnershipReport(payload) => Payload::OwnershipReport(payload),
+            PayloadView::TrafficReport(payload) => Payload::TrafficReport(payload),
+            PayloadView::OwnershipGeometricAltitude(payload) => {
+                Payload::OwnershipGeometricAltitude(payload)
+            }
+            PayloadView::HeightAboveTerrain(payload) => Payload::HeightAboveTerrain(payload),
+            PayloadView::BasicUatReport(payload) => Payload::BasicUatReport(payload),
+            PayloadView::LongUatReport(payload) => Payload::LongUatReport(payload),
+            PayloadView::ExtendedX65(payload) => Payload::ExtendedX65(payload),
+            PayloadView::StratuxHeartbeat(payload) => Payload::StratuxHeartbeat(payload),
+            PayloadView::StratuxStatus(payload) => Payload::StratuxStatus(payload),
+            PayloadView::LevilAHRS(payload) => Payload::LevilAHRS(payload),
+            PayloadView::Unknown { message_id, data } => Payload::Unknown(message_id, data),
+        })
+    }
+}
+
+fn to_array<const N: usize>(value: Vec<u8>) -> Result<[u8; N], String> {
+    let len = value.len();
+    value
+        .try_into()
+        .map_err(|_| format!("Expected {N} bytes, found {len}"))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{
+        extended::{ExtendedX65SubMessage, GeometricAltitudeDatum, IDMessage, InternetPolicy},
+        geometric::OwnershipGeometricAltitude,
+        initialization::Initialization,
+        levil::LevilAHRS,
+        message::{create_message, read_message},
+        passthrough::{BasicUatReport, LongUatReport},
+        payload::Payload,
+        stratux::{GpsFix, StratuxHeartbeat, StratuxStatus, StratuxTower},
+        terrain::HeightAboveTerrain,
+        uplink::UplinkData,
+    };
+
+    /// Decode a frame, check its JSON form and encode the deserialised payload again
+    fn round_trip(frame: &[u8], expected: serde_json::Value) {
+        let payload = read_message(frame).unwrap().get_payload().unwrap();
+
+        let value = serde_json::to_value(&payload).unwrap();
+        assert_eq!(value, expected);
+
+        let payload: Payload = serde_json::from_value(value).unwrap();
+        assert_eq!(&create_message(&payload).unwrap().encode()[..], frame);
+    }
+
+    /// Encode a payload and round trip its frame
+    fn round_trip_payload(payload: Payload, expected: serde_json::Value) {
+        round_trip(&create_message(&payload).unwrap().encode(), expected);
+    }
+
+    #[test]
+    fn test_heartbeat() {
+        round_trip(
+            &[
+                0x7E, 0x00, 0x81, 0x41, 0xDB, 0xD0, 0x08, 0x02, 0xB3, 0x8B, 0x7E,
+            ],
+            json!({
+                "HeartBeat": {
+                    "gps_pos_valid": true,
+                    "maint_req": false,
+                    "ident": false,
+                    "addr_type": false,
+                    "gps_batt_low": false,
+                    "ratcs": false,
+                    "uat_initialized": true,
+                    "csa_requested": true,
+                    "csa_not_available": false,
+                    "utc_ok": true,
+                    "time_stamp": 53467,
+                    "uplink_count": 1,
+                    "basic_long_count": 2,
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_traffic_report() {
+        let mut report = crate::report::Report::default();
+        report.participant_address = 0xABCDEF;
+        report.set_latitude(51.5);
+        report.set_longitude(-22.5);
+        report.set_altitude(3500);
+        report.set_horizontal_velocity(Some(120));
+        report.set_vertical_velocity(Some(-8));
+        report.set_track_heading(90.);
+        report.set_callsign(*b"N123    ");
+
+        let frame = create_message(&Payload::TrafficReport(report))
+            .unwrap()
+            .encode();
+
+        round_trip(
+            &frame,
+            json!({
+                "TrafficReport": {
+                    "traffic_alert_status": "NoAlert",
+                    "address_type": "ADSBWithICAOAddress",
+                    "participant_address": 0xABCDEF,
+                    "latitude": 51.5,
+                    "longitude": -22.5,
+                    "altitude": 3500,
+                    "heading_type": "NotValid",
+                    "report_type": "ReportIsUpdated",
+                    "flight_stage": "OnGround",
+                    "navigation_integrity_category": "Unknown",
+                    "navigation_accuracy_category_for_position": "Unknown",
+                    "horizontal_velocity": 120,
+                    "vertical_velocity": -512,
+                    "track_heading": 90.0,
+                    "emitter_category": "NoAircraftTypeInformation",
+                    "callsign": "N123",
+                    "emergency_priority_code": "NoEmergency",
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_extended_and_unknown() {
+        let frame = create_message(&Payload::Unknown(0x70, vec![0x01, 0x02]))
+            .unwrap()
+            .encode();
+        round_trip(
+            &frame,
+            json!({ "Unknown": { "message_id": 0x70, "data": [1, 2] } }),
+        );
+
+        let payload = Payload::ExtendedX65(
+            serde_json::from_value(json!({
+                "AHRS": {
+                    "roll": 10.0,
+                    "pitch": -10.0,
+                    "heading_type": "Magnetic",
+                    "heading": 90.0,
+                    "indicated_airspeed": 120,
+                    "true_airspeed": null,
+                }
+            }))
+            .unwrap(),
+        );
+        let frame = create_message(&payload).unwrap().encode();
+        round_trip(
+            &frame,
+            json!({
+                "ExtendedX65": {
+                    "AHRS": {
+                        "roll": 10.0,
+                        "pitch": -10.0,
+                        "heading_type": "Magnetic",
+                        "heading": 90.0,
+                        "indicated_airspeed": 120,
+                        "true_airspeed": null,
+                    }
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_initialization() {
+        let mut payload = Initialization::default();
+        payload.set_audio_inhibit(true);
+        payload.set_cdti_ok(true);
+        payload.set_csa_disable(true);
+
+        round_trip_payload(
+            Payload::Initialization(payload),
+            json!({
+                "Initialization": {
+                    "audio_test": false,
+                    "audio_inhibit": true,
+                    "cdti_ok": true,
+                    "csa_audio_disable": false,
+                    "csa_disable": true,
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_uplink_data() {
+        let mut uplink_payload = [0; 432];
+        uplink_payload[0] = 0x35;
+        uplink_payload[431] = 0x7E;
+
+        let mut payload = UplinkData::default();
+        payload.set_time_of_reception(Some(1000));
+        payload.set_uplink_payload(uplink_payload);
+
+        round_trip_payload(
+            Payload::UplinkData(payload),
+            json!({
+                "UplinkData": {
+                    "time_of_reception": 1000,
+                    "uplink_payload": uplink_payload.to_vec(),
+                }
+            }),
+        );
+
+        round_trip_payload(
+            Payload::UplinkData(UplinkData::default()),
+            json!({
+                "UplinkData": {
+                    "time_of_reception": null,
+                    "uplink_payload": vec![0; 432],
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_ownship_geometric_altitude() {
+        let mut payload = OwnershipGeometricAltitude::default();
+        payload.set_ownship_geo_altitude(3000);
+        payload.set_vertical_warning_indicator(true);
+        payload.set_vertical_figure_of_merit(Some(10));
+
+        round_trip_payload(
+            Payload::OwnershipGeometricAltitude(payload),
+            json!({
+                "OwnershipGeometricAltitude": {
+                    "ownship_geo_altitude": 3000,
+                    "vertical_warning_indicator": true,
+                    "vertical_figure_of_merit": 10,
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_height_above_terrain() {
+        let mut payload = HeightAboveTerrain::default();
+        payload.set_height_above_terrain(Some(-120));
+
+        round_trip_payload(
+            Payload::HeightAboveTerrain(payload),
+            json!({ "HeightAboveTerrain": { "height_above_terrain": -120 } }),
+        );
+
+        let mut payload = HeightAboveTerrain::default();
+        payload.set_height_above_terrain(None);
+
+        round_trip_payload(
+            Payload::HeightAboveTerrain(payload),
+            json!({ "HeightAboveTerrain": { "height_above_terrain": null } }),
+        );
+    }
+
+    #[test]
+    fn test_uat_reports() {
+        let mut basic = BasicUatReport::default();
+        basic.set_time_of_reception(Some(1000));
+        basic.set_payload([0x0A; 18]);
+
+        round_trip_payload(
+            Payload::BasicUatReport(basic),
+            json!({
+                "BasicUatReport": {
+                    "time_of_reception": 1000,
+                    "payload": vec![0x0A; 18],
+                }
+            }),
+        );
+
+        let mut long = LongUatReport::default();
+        long.set_time_of_reception(None);
+        long.set_payload([0x0B; 34]);
+
+        round_trip_payload(
+            Payload::LongUatReport(long),
+            json!({
+                "LongUatReport": {
+                    "time_of_reception": null,
+                    "payload": vec![0x0B; 34],
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_id_message() {
+        let mut payload = IDMessage::default();
+        payload.version = 1;
+        payload.device_serial_number = 0x0123456789;
+        payload.set_device_name("Stratux").unwrap();
+        payload.set_device_long_name("Stratux Europe").unwrap();
+        payload.set_geometric_altitude_datum(GeometricAltitudeDatum::MSL);
+        payload.set_internet_policy(InternetPolicy::Expensive);
+
+        round_trip_payload(
+            Payload::ExtendedX65(ExtendedX65SubMessage::IDMessage(payload)),
+            json!({
+                "ExtendedX65": {
+                    "IDMessage": {
+                        "version": 1,
+                        "device_serial_number": 0x0123456789_u64,
+                        "device_name": "Stratux",
+                        "device_long_name": "Stratux Europe",
+                        "geometric_altitude_datum": "MSL",
+                        "internet_policy": "Expensive",
+                    }
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_stratux() {
+        let mut heartbeat = StratuxHeartbeat::default();
+        heartbeat.set_gps_valid(true);
+
+        round_trip_payload(
+            Payload::StratuxHeartbeat(heartbeat),
+            json!({
+                "StratuxHeartbeat": {
+                    "protocol_version": 1,
+                    "gps_valid": true,
+                    "ahrs_valid": false,
+                }
+            }),
+        );
+
+        let mut tower = StratuxTower::default();
+        tower.set_latitude(51.5);
+        tower.set_longitude(-22.5);
+
+        let mut status = StratuxStatus::default();
+        status.set_firmware_version([1, 6, 0, 0]);
+        status.set_hardware_revision(Some(3));
+        status.set_gps_enabled(true);
+        status.set_uat_enabled(true);
+        status.set_gps_fix(GpsFix::Fix3D);
+        status.set_radios(2);
+        status.set_satellites_locked(8);
+        status.set_satellites_tracked(11);
+        status.set_uat_traffic_targets(4);
+        status.set_es_traffic_targets(12);
+        status.set_uat_messages_per_minute(300);
+        status.set_es_messages_per_minute(1500);
+        status.set_cpu_temperature(Some(45.5));
+        status.set_towers(vec![tower]);
+
+        round_trip_payload(
+            Payload::StratuxStatus(status),
+            json!({
+                "StratuxStatus": {
+                    "firmware_version": [1, 6, 0, 0],
+                    "hardware_revision": 3,
+                    "ahrs_enabled": false,
+                    "gps_enabled": true,
+                    "es_enabled": false,
+                    "uat_enabled": true,
+                    "pressure_altitude_valid": false,
+                    "ahrs_valid": false,
+                    "gps_fix": "Fix3D",
+                    "imu_connected": false,
+                    "radios": 2,
+                    "satellites_locked": 8,
+                    "satellites_tracked": 11,
+                    "uat_traffic_targets": 4,
+                    "es_traffic_targets": 12,
+                    "uat_messages_per_minute": 300,
+                    "es_messages_per_minute": 1500,
+                    "cpu_temperature": 45.5,
+                    "towers": [{ "latitude": 51.5, "longitude": -22.5 }],
+                }
+            }),
+        );
+    }
+
+    #[test]
+    fn test_levil_ahrs() {
+        let mut payload = LevilAHRS::default();
+        payload.set_roll(Some(10.5));
+        payload.set_pitch(Some(-2.5));
+        payload.set_heading(Some(270.));
+        payload.set_slip_skid(None);
+        payload.set_yaw_rate(Some(-3.));
+        payload.set_g_load(Some(1.5));
+        payload.set_indicated_airspeed(Some(95));
+        payload.set_pressure_altitude(Some(-200));
+        payload.set_vertical_speed(None);
+
+        round_trip_payload(
+            Payload::LevilAHRS(payload),
+            json!({
+                "LevilAHRS": {
+                    "roll": 10.5,
+                    "pitch": -2.5,
+                    "heading": 270.0,
+                    "slip_skid": null,
+                    "yaw_rate": -3.0,
+                    "g_load": 1.5,
+                    "indicated_airspeed": 95,
+                    "pressure_altitude": -200,
+                    "vertical_speed": null,
+                }
+            }),
+        );
+    }
+}